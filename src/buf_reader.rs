@@ -0,0 +1,156 @@
+use std::{cmp, fmt};
+use std::io::{self, Read, Write};
+
+use futures::{Async, Poll};
+use iovec::IoVec;
+
+use {AsyncBufRead, AsyncRead, AsyncWrite};
+
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+/// Adds buffering to any reader, in a nonblocking and futures-aware fashion.
+///
+/// This is the asynchronous counterpart of [`std::io::BufReader`]. It reads
+/// large, infrequent batches from the underlying [`AsyncRead`] and exposes them
+/// through [`AsyncBufRead`]. Unlike the `std` version, a "would block" error
+/// from the inner reader never disturbs data that is already buffered.
+///
+/// Note that any data remaining in the internal buffer is lost when the
+/// `BufReader` is dropped or unwrapped with [`into_inner`].
+///
+/// [`std::io::BufReader`]: https://doc.rust-lang.org/std/io/struct.BufReader.html
+/// [`AsyncRead`]: ../trait.AsyncRead.html
+/// [`AsyncBufRead`]: ../trait.AsyncBufRead.html
+/// [`into_inner`]: #method.into_inner
+pub struct BufReader<R> {
+    inner: R,
+    buf: Box<[u8]>,
+    pos: usize,
+    cap: usize,
+}
+
+impl<R: AsyncRead> BufReader<R> {
+    /// Creates a new `BufReader` with a default buffer capacity.
+    pub fn new(inner: R) -> BufReader<R> {
+        BufReader::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Creates a new `BufReader` with the specified buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: R) -> BufReader<R> {
+        with_buffer(vec![0; capacity].into_boxed_slice(), inner)
+    }
+}
+
+fn with_buffer<R>(buf: Box<[u8]>, inner: R) -> BufReader<R> {
+    BufReader {
+        inner: inner,
+        buf: buf,
+        pos: 0,
+        cap: 0,
+    }
+}
+
+impl<R> BufReader<R> {
+    /// Gets a reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the currently buffered data.
+    ///
+    /// Unlike `poll_fill_buf`, this will not attempt to fill the buffer if it
+    /// is empty.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.cap]
+    }
+
+    /// Consumes this `BufReader`, returning the underlying reader.
+    ///
+    /// Note that any leftover data in the internal buffer is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> Read for BufReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // If we don't have any buffered data and we're doing a massive read
+        // (larger than our internal buffer), bypass our internal buffer
+        // entirely.
+        if self.pos == self.cap && buf.len() >= self.buf.len() {
+            return self.inner.read(buf);
+        }
+
+        if self.pos >= self.cap {
+            self.cap = try!(self.inner.read(&mut self.buf));
+            self.pos = 0;
+        }
+
+        let n = cmp::min(buf.len(), self.cap - self.pos);
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for BufReader<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: AsyncRead> AsyncBufRead for BufReader<R> {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], io::Error> {
+        // If we've reached the end of our internal buffer then we need to
+        // fetch some more data from the underlying reader. A "would block"
+        // leaves the (empty) buffer untouched.
+        if self.pos >= self.cap {
+            self.cap = try_nb!(self.inner.read(&mut self.buf));
+            self.pos = 0;
+        }
+
+        Ok(Async::Ready(&self.buf[self.pos..self.cap]))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.cap);
+    }
+}
+
+impl<R: Write> Write for BufReader<R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: AsyncWrite> AsyncWrite for BufReader<R> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        self.inner.write_bufs(bufs)
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for BufReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufReader")
+            .field("reader", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.cap - self.pos, self.buf.len()))
+            .finish()
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use futures::{Async, Poll};
+use iovec::IoVec;
+
+use {AsyncRead, AsyncWrite};
+
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+/// Wraps a writer and buffers its output, in a nonblocking and futures-aware
+/// fashion.
+///
+/// This is the asynchronous counterpart of [`std::io::BufWriter`]. Small
+/// writes are gathered in memory and handed to the underlying [`AsyncWrite`]
+/// in large batches. If the underlying writer returns a "would block" error
+/// part way through emptying the buffer, the bytes that were accepted are
+/// dropped from the buffer and the rest are kept for the next attempt.
+///
+/// Buffered data is **not** written out when the `BufWriter` is dropped. Use
+/// `flush` or `shutdown` to make sure everything reaches the underlying writer.
+///
+/// [`std::io::BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+/// [`AsyncWrite`]: ../trait.AsyncWrite.html
+pub struct BufWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+    cap: usize,
+}
+
+impl<W: AsyncWrite> BufWriter<W> {
+    /// Creates a new `BufWriter` with a default buffer capacity.
+    pub fn new(inner: W) -> BufWriter<W> {
+        BufWriter::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Creates a new `BufWriter` with the specified buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: W) -> BufWriter<W> {
+        BufWriter {
+            inner: inner,
+            buf: Vec::with_capacity(capacity),
+            cap: capacity,
+        }
+    }
+
+    // Writes out as much of the buffer as the underlying writer accepts. A
+    // "would block" error is returned as is, with the unwritten tail of the
+    // buffer kept in place.
+    fn flush_buf(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let len = self.buf.len();
+        let mut ret = Ok(());
+
+        while written < len {
+            match self.inner.write(&self.buf[written..]) {
+                Ok(0) => {
+                    ret = Err(io::Error::new(io::ErrorKind::WriteZero,
+                                             "failed to write the buffered data"));
+                    break;
+                }
+                Ok(n) => written += n,
+                Err(e) => {
+                    ret = Err(e);
+                    break;
+                }
+            }
+        }
+
+        if written > 0 {
+            self.buf.drain(..written);
+        }
+
+        ret
+    }
+}
+
+impl<W> BufWriter<W> {
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the internally buffered data.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Consumes this `BufWriter`, returning the underlying writer.
+    ///
+    /// Note that any data that has not been flushed yet is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> Write for BufWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + buf.len() > self.cap {
+            try!(self.flush_buf());
+        }
+
+        if buf.len() >= self.cap {
+            self.inner.write(buf)
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        try!(self.flush_buf());
+        self.inner.flush()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BufWriter<W> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        try_nb!(self.flush_buf());
+        self.inner.shutdown()
+    }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        let total = bufs.iter().fold(0, |n, buf| n + buf.len());
+
+        if self.buf.len() + total > self.cap {
+            try_nb!(self.flush_buf());
+        }
+
+        if total >= self.cap {
+            self.inner.write_bufs(bufs)
+        } else {
+            for buf in bufs {
+                self.buf.extend_from_slice(buf);
+            }
+            Ok(Async::Ready(total))
+        }
+    }
+}
+
+impl<W: Read> Read for BufWriter<W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<W: AsyncRead> AsyncRead for BufWriter<W> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        self.inner.read_bufs(bufs)
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for BufWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufWriter")
+            .field("writer", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.buf.len(), self.cap))
+            .finish()
+    }
+}
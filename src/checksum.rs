@@ -0,0 +1,278 @@
+use std::{cmp, fmt, io};
+use std::error::Error as StdError;
+
+use bytes::{Buf, BigEndian, ByteOrder, LittleEndian};
+use iovec::IoVec;
+
+/// Checksum algorithms for the trailer of length delimited frames.
+///
+/// See `Builder::checksum` for more detail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checksum {
+    /// CRC-32 (IEEE 802.3), as used by zlib, gzip and Ethernet.
+    Crc32,
+
+    /// CRC-32C (Castagnoli), as used by iSCSI, SCTP and ext4.
+    Crc32c,
+}
+
+/// An error when the checksum trailer of a frame does not match its contents.
+///
+/// Returned as the payload of an `io::Error` of kind `InvalidData`, the same
+/// kind as other malformed input. Use `ChecksumMismatch::from_io_error` to
+/// tell it apart.
+#[derive(Debug)]
+pub struct ChecksumMismatch {
+    frame: u64,
+    expected: u32,
+    actual: u32,
+}
+
+// Number of bytes in a checksum trailer
+pub const TRAILER_LEN: usize = 4;
+
+// Tables for the reflected polynomials, processing 4 bits at a time.
+const CRC32_TABLE: [u32; 16] = [
+    0x00000000, 0x1db71064, 0x3b6e20c8, 0x26d930ac,
+    0x76dc4190, 0x6b6b51f4, 0x4db26158, 0x5005713c,
+    0xedb88320, 0xf00f9344, 0xd6d6a3e8, 0xcb61b38c,
+    0x9b64c2b0, 0x86d3d2d4, 0xa00ae278, 0xbdbdf21c,
+];
+
+const CRC32C_TABLE: [u32; 16] = [
+    0x00000000, 0x105ec76f, 0x20bd8ede, 0x30e349b1,
+    0x417b1dbc, 0x5125dad3, 0x61c69362, 0x7198540d,
+    0x82f63b78, 0x92a8fc17, 0xa24bb5a6, 0xb21572c9,
+    0xc38d26c4, 0xd3d3e1ab, 0xe330a81a, 0xf36e6f75,
+];
+
+// ===== impl Checksum =====
+
+impl Checksum {
+    /// Computes the checksum of `data`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::length_delimited::Checksum;
+    ///
+    /// assert_eq!(Checksum::Crc32.compute(b"123456789"), 0xcbf43926);
+    /// assert_eq!(Checksum::Crc32c.compute(b"123456789"), 0xe3069283);
+    /// ```
+    pub fn compute(&self, data: &[u8]) -> u32 {
+        !update(*self, !0, data)
+    }
+}
+
+// Feeds `data` into the running (non inverted) state of a checksum.
+pub fn update(checksum: Checksum, mut crc: u32, data: &[u8]) -> u32 {
+    let table = match checksum {
+        Checksum::Crc32 => &CRC32_TABLE,
+        Checksum::Crc32c => &CRC32C_TABLE,
+    };
+
+    for &b in data {
+        crc ^= b as u32;
+        crc = (crc >> 4) ^ table[(crc & 0xf) as usize];
+        crc = (crc >> 4) ^ table[(crc & 0xf) as usize];
+    }
+
+    crc
+}
+
+pub fn encode_trailer(crc: u32, big_endian: bool) -> [u8; TRAILER_LEN] {
+    let mut trailer = [0; TRAILER_LEN];
+
+    if big_endian {
+        BigEndian::write_u32(&mut trailer, crc);
+    } else {
+        LittleEndian::write_u32(&mut trailer, crc);
+    }
+
+    trailer
+}
+
+pub fn decode_trailer(src: &[u8], big_endian: bool) -> u32 {
+    if big_endian {
+        BigEndian::read_u32(src)
+    } else {
+        LittleEndian::read_u32(src)
+    }
+}
+
+pub fn mismatch(frame: u64, expected: u32, actual: u32) -> ChecksumMismatch {
+    ChecksumMismatch {
+        frame: frame,
+        expected: expected,
+        actual: actual,
+    }
+}
+
+// ===== impl ChecksumMismatch =====
+
+impl ChecksumMismatch {
+    /// Returns the `ChecksumMismatch` carried by `err`, if it is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use tokio_io::codec::length_delimited::ChecksumMismatch;
+    ///
+    /// # fn handle(err: io::Error) {
+    /// if let Some(mismatch) = ChecksumMismatch::from_io_error(&err) {
+    ///     println!("corrupted frame {}", mismatch.frame());
+    /// }
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    pub fn from_io_error(err: &io::Error) -> Option<&ChecksumMismatch> {
+        err.get_ref().and_then(|inner| inner.downcast_ref::<ChecksumMismatch>())
+    }
+
+    /// Returns the index of the frame that failed, the first frame read being
+    /// frame 0.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Returns the checksum found in the frame trailer.
+    pub fn expected(&self) -> u32 {
+        self.expected
+    }
+
+    /// Returns the checksum computed from the frame contents.
+    pub fn actual(&self) -> u32 {
+        self.actual
+    }
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} in frame {}: expected {:#010x}, computed {:#010x}",
+               self.description(), self.frame, self.expected, self.actual)
+    }
+}
+
+impl StdError for ChecksumMismatch {
+    fn description(&self) -> &str {
+        "frame checksum mismatch"
+    }
+}
+
+/// A `Buf` followed by a trailer holding its checksum.
+///
+/// The checksum of a contiguous buffer is computed right away, so that the
+/// trailer can be written along with it. Otherwise, it is computed as the
+/// bytes are consumed.
+#[derive(Debug)]
+pub struct ChecksumBuf<B> {
+    inner: B,
+    checksum: Option<Checksum>,
+    big_endian: bool,
+    crc: u32,
+    finished: bool,
+    trailer: [u8; TRAILER_LEN],
+    pos: usize,
+}
+
+pub fn checksum_buf<B: Buf>(inner: B, checksum: Option<Checksum>, big_endian: bool)
+    -> ChecksumBuf<B>
+{
+    let mut buf = ChecksumBuf {
+        inner: inner,
+        checksum: checksum,
+        big_endian: big_endian,
+        crc: !0,
+        finished: false,
+        trailer: [0; TRAILER_LEN],
+        pos: 0,
+    };
+
+    if buf.inner.bytes().len() == buf.inner.remaining() {
+        if let Some(checksum) = checksum {
+            buf.crc = update(checksum, buf.crc, buf.inner.bytes());
+        }
+        buf.finish();
+    }
+
+    buf
+}
+
+impl<B> ChecksumBuf<B> {
+    fn trailer_len(&self) -> usize {
+        if self.checksum.is_some() {
+            TRAILER_LEN
+        } else {
+            0
+        }
+    }
+
+    fn finish(&mut self) {
+        self.trailer = encode_trailer(!self.crc, self.big_endian);
+        self.finished = true;
+    }
+}
+
+impl<B: Buf> Buf for ChecksumBuf<B> {
+    fn remaining(&self) -> usize {
+        self.inner.remaining() + self.trailer_len() - self.pos
+    }
+
+    fn bytes(&self) -> &[u8] {
+        if self.inner.has_remaining() {
+            self.inner.bytes()
+        } else {
+            &self.trailer[self.pos..self.trailer_len()]
+        }
+    }
+
+    fn bytes_vec<'a>(&'a self, dst: &mut [&'a IoVec]) -> usize {
+        let mut n = 0;
+
+        if self.inner.has_remaining() {
+            n = self.inner.bytes_vec(dst);
+
+            // The trailer can only follow the whole inner buffer, once it is
+            // known
+            let len = dst[..n].iter().fold(0, |len, buf| len + buf.len());
+            if len < self.inner.remaining() || !self.finished {
+                return n;
+            }
+        }
+
+        if n < dst.len() && self.pos < self.trailer_len() {
+            dst[n] = self.trailer[self.pos..self.trailer_len()].into();
+            n += 1;
+        }
+
+        n
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        // Advance the inner buffer one chunk at a time, so every byte goes
+        // through the checksum.
+        while cnt > 0 && self.inner.has_remaining() {
+            let n = {
+                let chunk = self.inner.bytes();
+                let n = cmp::min(cnt, chunk.len());
+
+                if let (Some(checksum), false) = (self.checksum, self.finished) {
+                    self.crc = update(checksum, self.crc, &chunk[..n]);
+                }
+
+                n
+            };
+
+            self.inner.advance(n);
+            cnt -= n;
+
+            if !self.inner.has_remaining() && !self.finished {
+                self.finish();
+            }
+        }
+
+        assert!(self.pos + cnt <= self.trailer_len());
+        self.pos += cnt;
+    }
+}
@@ -10,10 +10,14 @@
 //! [`Stream`]: #
 //! [transports]: #
 
+pub use codec_adapters::{AndThen, Map, MapErr, Recover, With};
 pub use codecs::{BytesCodec, LinesCodec};
-pub use framed::{Framed, FramedParts};
-pub use framed_read::{FramedRead, Decoder};
-pub use framed_write::{FramedWrite, Encoder};
+pub use encode_buf::EncodeBuf;
+pub use framed::{Builder, Framed, FramedParts};
+pub use framed_read::{FramedRead, FramedReadParts, Decoder};
+pub use framed_split::{FramedSinkHalf, FramedStreamHalf, ReuniteError};
+pub use framed_write::{FramedWrite, FramedWriteParts, Encoder};
+pub use layered::Layered;
 
 pub mod length_delimited {
     //! Frame a stream of bytes based on a length prefix
@@ -348,11 +352,21 @@ pub mod length_delimited {
     //! +------------+--------------+
     //! ```
     //!
+    //! # Using the codec
+    //!
+    //! The same framing is available as a [`LengthDelimitedCodec`], created by
+    //! `Builder::new_codec`. It implements both [`Decoder`] and [`Encoder`], so
+    //! it can be combined with the generic `codec` types and with other codecs,
+    //! for example with [`Layered`].
+    //!
     //! [`FramedRead`]: struct.FramedRead.html
     //! [`FramedWrite`]: struct.FramedWrite.html
     //! [`AsyncRead`]: ../../trait.AsyncRead.html
     //! [`AsyncWrite`]: ../../trait.AsyncWrite.html
     //! [`Encoder`]: ../trait.Encoder.html
+    //! [`Decoder`]: ../trait.Decoder.html
+    //! [`LengthDelimitedCodec`]: struct.LengthDelimitedCodec.html
+    //! [`Layered`]: ../struct.Layered.html
     //! [`BytesMut`]: https://docs.rs/bytes/~0.4/bytes/struct.BytesMut.html
 
     pub use ::length_delimited::*;
@@ -0,0 +1,372 @@
+use std::io;
+use std::marker::PhantomData;
+
+use bytes::BytesMut;
+
+use codec::{Decoder, Encoder, EncodeBuf};
+
+/// Codec for the `Decoder::map` combinator, mapping decoded items with a
+/// function.
+///
+/// Encoding is passed through to the underlying codec.
+#[derive(Debug, Clone)]
+pub struct Map<C, F> {
+    codec: C,
+    f: F,
+}
+
+/// Codec for the `Decoder::map_err` combinator, mapping the errors of a codec
+/// with a function.
+///
+/// The function is applied to both decoding and encoding errors.
+#[derive(Debug, Clone)]
+pub struct MapErr<C, F> {
+    codec: C,
+    f: F,
+}
+
+/// Codec for the `Decoder::and_then` combinator, passing decoded items through
+/// a fallible function.
+///
+/// Encoding is passed through to the underlying codec.
+#[derive(Debug, Clone)]
+pub struct AndThen<C, F> {
+    codec: C,
+    f: F,
+}
+
+/// Codec for the `Encoder::with` combinator, mapping items with a function
+/// before encoding them.
+///
+/// Decoding is passed through to the underlying codec.
+#[derive(Debug)]
+pub struct With<C, F, U> {
+    codec: C,
+    f: F,
+    _marker: PhantomData<fn(U)>,
+}
+
+/// Codec for the `Decoder::recover` combinator, yielding decoding errors as
+/// items and resynchronising after them.
+///
+/// Encoding is passed through to the underlying codec.
+#[derive(Debug, Clone)]
+pub struct Recover<C> {
+    codec: C,
+
+    // Whether an error was yielded and `resync` did not find a sync point yet
+    resyncing: bool,
+}
+
+pub fn map<C, F>(codec: C, f: F) -> Map<C, F> {
+    Map {
+        codec: codec,
+        f: f,
+    }
+}
+
+pub fn map_err<C, F>(codec: C, f: F) -> MapErr<C, F> {
+    MapErr {
+        codec: codec,
+        f: f,
+    }
+}
+
+pub fn and_then<C, F>(codec: C, f: F) -> AndThen<C, F> {
+    AndThen {
+        codec: codec,
+        f: f,
+    }
+}
+
+pub fn with<C, F, U>(codec: C, f: F) -> With<C, F, U> {
+    With {
+        codec: codec,
+        f: f,
+        _marker: PhantomData,
+    }
+}
+
+pub fn recover<C>(codec: C) -> Recover<C> {
+    Recover {
+        codec: codec,
+        resyncing: false,
+    }
+}
+
+macro_rules! accessors {
+    ($name:ident) => {
+        impl<C, F> $name<C, F> {
+            /// Returns a reference to the underlying codec.
+            pub fn get_ref(&self) -> &C {
+                &self.codec
+            }
+
+            /// Returns a mutable reference to the underlying codec.
+            pub fn get_mut(&mut self) -> &mut C {
+                &mut self.codec
+            }
+
+            /// Consumes the combinator, returning the underlying codec.
+            pub fn into_inner(self) -> C {
+                self.codec
+            }
+        }
+    }
+}
+
+accessors!(Map);
+accessors!(MapErr);
+accessors!(AndThen);
+
+// ===== impl Map =====
+
+impl<C, F, U> Decoder for Map<C, F>
+    where C: Decoder,
+          F: FnMut(C::Item) -> U,
+{
+    type Item = U;
+    type Error = C::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<U>, C::Error> {
+        Ok(try!(self.codec.decode(src)).map(&mut self.f))
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<U>, C::Error> {
+        Ok(try!(self.codec.decode_eof(buf)).map(&mut self.f))
+    }
+
+    fn resync(&mut self, buf: &mut BytesMut) -> Result<bool, C::Error> {
+        self.codec.resync(buf)
+    }
+}
+
+impl<C: Encoder, F> Encoder for Map<C, F> {
+    type Item = C::Item;
+    type Error = C::Error;
+
+    fn encode(&mut self, item: C::Item, dst: &mut BytesMut) -> Result<(), C::Error> {
+        self.codec.encode(item, dst)
+    }
+
+    fn encode_to(&mut self, item: C::Item, dst: &mut EncodeBuf) -> Result<(), C::Error> {
+        self.codec.encode_to(item, dst)
+    }
+}
+
+// ===== impl MapErr =====
+
+impl<C, F, E> Decoder for MapErr<C, F>
+    where C: Decoder,
+          F: FnMut(<C as Decoder>::Error) -> E,
+          E: From<io::Error>,
+{
+    type Item = <C as Decoder>::Item;
+    type Error = E;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, E> {
+        self.codec.decode(src).map_err(&mut self.f)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, E> {
+        self.codec.decode_eof(buf).map_err(&mut self.f)
+    }
+
+    fn resync(&mut self, buf: &mut BytesMut) -> Result<bool, E> {
+        self.codec.resync(buf).map_err(&mut self.f)
+    }
+}
+
+impl<C, F, E> Encoder for MapErr<C, F>
+    where C: Encoder,
+          F: FnMut(<C as Encoder>::Error) -> E,
+          E: From<io::Error>,
+{
+    type Item = <C as Encoder>::Item;
+    type Error = E;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut BytesMut) -> Result<(), E> {
+        self.codec.encode(item, dst).map_err(&mut self.f)
+    }
+
+    fn encode_to(&mut self, item: Self::Item, dst: &mut EncodeBuf) -> Result<(), E> {
+        self.codec.encode_to(item, dst).map_err(&mut self.f)
+    }
+}
+
+// ===== impl AndThen =====
+
+impl<C, F, U> Decoder for AndThen<C, F>
+    where C: Decoder,
+          F: FnMut(C::Item) -> Result<U, C::Error>,
+{
+    type Item = U;
+    type Error = C::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<U>, C::Error> {
+        match try!(self.codec.decode(src)) {
+            Some(item) => (self.f)(item).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<U>, C::Error> {
+        match try!(self.codec.decode_eof(buf)) {
+            Some(item) => (self.f)(item).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn resync(&mut self, buf: &mut BytesMut) -> Result<bool, C::Error> {
+        self.codec.resync(buf)
+    }
+}
+
+impl<C: Encoder, F> Encoder for AndThen<C, F> {
+    type Item = C::Item;
+    type Error = C::Error;
+
+    fn encode(&mut self, item: C::Item, dst: &mut BytesMut) -> Result<(), C::Error> {
+        self.codec.encode(item, dst)
+    }
+
+    fn encode_to(&mut self, item: C::Item, dst: &mut EncodeBuf) -> Result<(), C::Error> {
+        self.codec.encode_to(item, dst)
+    }
+}
+
+// ===== impl With =====
+
+impl<C, F, U> With<C, F, U> {
+    /// Returns a reference to the underlying codec.
+    pub fn get_ref(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the underlying codec.
+    pub fn get_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Consumes the combinator, returning the underlying codec.
+    pub fn into_inner(self) -> C {
+        self.codec
+    }
+}
+
+impl<C, F, U> Encoder for With<C, F, U>
+    where C: Encoder,
+          F: FnMut(U) -> C::Item,
+{
+    type Item = U;
+    type Error = C::Error;
+
+    fn encode(&mut self, item: U, dst: &mut BytesMut) -> Result<(), C::Error> {
+        let item = (self.f)(item);
+        self.codec.encode(item, dst)
+    }
+
+    fn encode_to(&mut self, item: U, dst: &mut EncodeBuf) -> Result<(), C::Error> {
+        let item = (self.f)(item);
+        self.codec.encode_to(item, dst)
+    }
+}
+
+impl<C: Decoder, F, U> Decoder for With<C, F, U> {
+    type Item = C::Item;
+    type Error = C::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<C::Item>, C::Error> {
+        self.codec.decode(src)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<C::Item>, C::Error> {
+        self.codec.decode_eof(buf)
+    }
+
+    fn resync(&mut self, buf: &mut BytesMut) -> Result<bool, C::Error> {
+        self.codec.resync(buf)
+    }
+}
+
+impl<C: Clone, F: Clone, U> Clone for With<C, F, U> {
+    fn clone(&self) -> With<C, F, U> {
+        With {
+            codec: self.codec.clone(),
+            f: self.f.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+// ===== impl Recover =====
+
+impl<C> Recover<C> {
+    /// Returns a reference to the underlying codec.
+    pub fn get_ref(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the underlying codec.
+    pub fn get_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Consumes the combinator, returning the underlying codec.
+    pub fn into_inner(self) -> C {
+        self.codec
+    }
+}
+
+impl<C: Decoder> Decoder for Recover<C> {
+    type Item = Result<C::Item, C::Error>;
+    type Error = C::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, C::Error> {
+        if self.resyncing {
+            if !try!(self.codec.resync(src)) {
+                return Ok(None);
+            }
+            self.resyncing = false;
+        }
+
+        match self.codec.decode(src) {
+            Ok(item) => Ok(item.map(Ok)),
+            Err(e) => {
+                self.resyncing = true;
+                Ok(Some(Err(e)))
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, C::Error> {
+        if self.resyncing {
+            // The bytes left before the end of the stream are dropped
+            if !try!(self.codec.resync(buf)) {
+                return Ok(None);
+            }
+            self.resyncing = false;
+        }
+
+        match self.codec.decode_eof(buf) {
+            Ok(item) => Ok(item.map(Ok)),
+            Err(e) => {
+                self.resyncing = true;
+                Ok(Some(Err(e)))
+            }
+        }
+    }
+}
+
+impl<C: Encoder> Encoder for Recover<C> {
+    type Item = C::Item;
+    type Error = C::Error;
+
+    fn encode(&mut self, item: C::Item, dst: &mut BytesMut) -> Result<(), C::Error> {
+        self.codec.encode(item, dst)
+    }
+
+    fn encode_to(&mut self, item: C::Item, dst: &mut EncodeBuf) -> Result<(), C::Error> {
+        self.codec.encode_to(item, dst)
+    }
+}
@@ -1,5 +1,5 @@
 use bytes::{Bytes, BufMut, BytesMut};
-use codec::{Encoder, Decoder};
+use codec::{Encoder, Decoder, EncodeBuf};
 use std::{io, str};
 
 /// A simple `Codec` implementation that just ships bytes around.
@@ -34,6 +34,11 @@ impl Encoder for BytesCodec {
         buf.put(data);
         Ok(())
     }
+
+    fn encode_to(&mut self, data: Bytes, buf: &mut EncodeBuf) -> Result<(), io::Error> {
+        buf.put_bytes(data);
+        Ok(())
+    }
 }
 
 /// A simple `Codec` implementation that splits up data into lines.
@@ -108,6 +113,13 @@ impl Decoder for LinesCodec {
             }
         })
     }
+
+    fn resync(&mut self, _buf: &mut BytesMut) -> Result<bool, io::Error> {
+        // Errors only happen once a line was split off the buffer, so `buf`
+        // already starts at the next line.
+        self.next_index = 0;
+        Ok(true)
+    }
 }
 
 impl Encoder for LinesCodec {
@@ -1,24 +1,100 @@
-use std::io;
+use std::{cmp, fmt, io, mem};
+use std::io::Read;
 
-use futures::{Future, Poll};
+use futures::{Async, Future, Poll};
 
-use {AsyncRead, AsyncWrite};
+use {AsyncBufRead, AsyncRead, AsyncWrite};
+use copy_buf::poll_copy_buf;
+
+const DEFAULT_BUF_SIZE: usize = 2048;
 
 /// A future which will copy all data from a reader into a writer.
 ///
-/// Created by the [`copy`] function, this future will resolve to the number of
-/// bytes copied or an error if one happens.
+/// Created by the [`copy`] function or by a [`CopyBuilder`], this future will
+/// resolve to the number of bytes copied or an error if one happens.
 ///
 /// [`copy`]: fn.copy.html
-#[derive(Debug)]
-pub struct Copy<R, W> {
+/// [`CopyBuilder`]: struct.CopyBuilder.html
+pub struct Copy<R, W, F = fn(u64)> {
+    inner: CopyWithBuffer<R, W, F>,
+}
+
+/// A future which will copy all data from a reader into a writer through a
+/// caller-provided buffer, and hand the buffer back.
+///
+/// Created by [`CopyBuilder::copy_with_buffer`], this future will resolve to
+/// the number of bytes copied, the reader, the writer and the buffer, or an
+/// error if one happens.
+///
+/// [`CopyBuilder::copy_with_buffer`]: struct.CopyBuilder.html#method.copy_with_buffer
+pub struct CopyWithBuffer<R, W, F = fn(u64)> {
     reader: Option<R>,
-    read_done: bool,
     writer: Option<W>,
-    pos: usize,
-    cap: usize,
+    transfer: Transfer,
+    progress: F,
+}
+
+/// Configures and creates [`Copy`] futures.
+///
+/// The builder controls the size of the intermediate buffer, or supplies the
+/// buffer itself, and can cap the number of bytes transferred.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_io;
+/// # extern crate futures;
+/// # use futures::Future;
+/// # fn main() {
+/// use tokio_io::io::CopyBuilder;
+/// use std::io::Cursor;
+///
+/// let reader: &[u8] = b"hello world";
+/// let writer = Cursor::new(Vec::new());
+///
+/// let (amt, reader, writer) = CopyBuilder::new()
+///     .buffer_size(4)
+///     .limit(5)
+///     .copy(reader, writer)
+///     .wait().unwrap();
+///
+/// assert_eq!(amt, 5);
+/// assert_eq!(reader, b" world");
+/// assert_eq!(writer.get_ref(), b"hello");
+/// # }
+/// ```
+///
+/// [`Copy`]: struct.Copy.html
+#[derive(Debug, Clone, Copy)]
+pub struct CopyBuilder {
+    buffer_size: usize,
+    limit: u64,
+}
+
+// Copies bytes from a reader into a writer through an intermediate buffer,
+// reading at most `limit` bytes.
+#[derive(Debug)]
+pub struct Transfer {
+    buf: Buffer,
     amt: u64,
+    read_done: bool,
+}
+
+// The intermediate buffer of a `Transfer`.
+#[derive(Debug)]
+struct Buffer {
     buf: Box<[u8]>,
+    pos: usize,
+    cap: usize,
+    // Number of bytes read so far, and the most that may be read
+    read: u64,
+    limit: u64,
+}
+
+// A reader seen through the buffer of a `Transfer`, to feed `poll_copy_buf`.
+struct Fill<'a, R: 'a> {
+    buf: &'a mut Buffer,
+    reader: &'a mut R,
 }
 
 /// Creates a future which represents copying all the bytes from one object to
@@ -32,65 +108,289 @@ pub struct Copy<R, W> {
 /// On success the number of bytes is returned and the `reader` and `writer` are
 /// consumed. On error the error is returned and the I/O objects are consumed as
 /// well.
+///
+/// Use a [`CopyBuilder`] to tune the buffer or stop after a given number of
+/// bytes. If `reader` already implements [`AsyncBufRead`], [`copy_buf`] avoids
+/// the intermediate buffer allocated by this function.
+///
+/// [`CopyBuilder`]: struct.CopyBuilder.html
+/// [`AsyncBufRead`]: ../trait.AsyncBufRead.html
+/// [`copy_buf`]: fn.copy_buf.html
 pub fn copy<R, W>(reader: R, writer: W) -> Copy<R, W>
     where R: AsyncRead,
           W: AsyncWrite,
 {
-    Copy {
-        reader: Some(reader),
-        read_done: false,
-        writer: Some(writer),
-        amt: 0,
-        pos: 0,
-        cap: 0,
-        buf: Box::new([0; 2048]),
+    CopyBuilder::new().copy(reader, writer)
+}
+
+// ===== impl CopyBuilder =====
+
+impl CopyBuilder {
+    /// Creates a new `CopyBuilder` with the default configuration.
+    ///
+    /// * buffer_size: 2KB
+    /// * limit: none
+    pub fn new() -> CopyBuilder {
+        CopyBuilder {
+            buffer_size: DEFAULT_BUF_SIZE,
+            limit: u64::max_value(),
+        }
+    }
+
+    /// Sets the size of the buffer allocated by `copy`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `size` is zero.
+    pub fn buffer_size(&mut self, size: usize) -> &mut Self {
+        assert!(size > 0, "copy buffer size must be greater than zero");
+        self.buffer_size = size;
+        self
+    }
+
+    /// Sets the maximum number of bytes to copy.
+    ///
+    /// Once `limit` bytes have been copied the transfer completes as if the
+    /// reader had hit EOF. The reader is never asked for more bytes than the
+    /// limit allows, so whatever follows stays in the reader, which is handed
+    /// back when the future completes.
+    pub fn limit(&mut self, limit: u64) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Creates a future copying from `reader` into `writer` with the current
+    /// configuration.
+    pub fn copy<R, W>(&self, reader: R, writer: W) -> Copy<R, W>
+        where R: AsyncRead,
+              W: AsyncWrite,
+    {
+        let buf = vec![0; self.buffer_size].into_boxed_slice();
+        Copy {
+            inner: self.copy_with_buffer(buf, reader, writer),
+        }
+    }
+
+    /// Creates a future copying from `reader` into `writer` through the
+    /// provided buffer, instead of allocating a new one.
+    ///
+    /// The configured `buffer_size` is ignored. The buffer is handed back
+    /// once the copy completes, so that it can be reused for the next one.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `buf` is empty.
+    pub fn copy_with_buffer<R, W>(&self, buf: Box<[u8]>, reader: R, writer: W)
+        -> CopyWithBuffer<R, W>
+        where R: AsyncRead,
+              W: AsyncWrite,
+    {
+        assert!(buf.len() > 0, "copy buffer must not be empty");
+        CopyWithBuffer {
+            reader: Some(reader),
+            writer: Some(writer),
+            transfer: transfer(buf, self.limit),
+            progress: ignore_progress,
+        }
+    }
+}
+
+impl Default for CopyBuilder {
+    fn default() -> CopyBuilder {
+        CopyBuilder::new()
+    }
+}
+
+fn ignore_progress(_: u64) {}
+
+// ===== impl Copy =====
+
+impl<R, W, F> Copy<R, W, F> {
+    /// Calls `f` every time bytes are written to the writer.
+    ///
+    /// The callback receives the total number of bytes copied so far.
+    pub fn progress<G>(self, f: G) -> Copy<R, W, G>
+        where G: FnMut(u64),
+    {
+        Copy {
+            inner: self.inner.progress(f),
+        }
+    }
+
+    /// Returns the number of bytes copied so far.
+    pub fn amount(&self) -> u64 {
+        self.inner.amount()
     }
 }
 
-impl<R, W> Future for Copy<R, W>
+impl<R, W, F> Future for Copy<R, W, F>
     where R: AsyncRead,
           W: AsyncWrite,
+          F: FnMut(u64),
 {
     type Item = (u64, R, W);
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<(u64, R, W), io::Error> {
-        loop {
-            // If our buffer is empty, then we need to read some data to
-            // continue.
-            if self.pos == self.cap && !self.read_done {
-                let reader = self.reader.as_mut().unwrap();
-                let n = try_nb!(reader.read(&mut self.buf));
-                if n == 0 {
-                    self.read_done = true;
-                } else {
-                    self.pos = 0;
-                    self.cap = n;
-                }
-            }
+        let (amt, reader, writer, _) = try_ready!(self.inner.poll());
+        Ok((amt, reader, writer).into())
+    }
+}
+
+impl<R: fmt::Debug, W: fmt::Debug, F> fmt::Debug for Copy<R, W, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Copy")
+            .field("reader", &self.inner.reader)
+            .field("writer", &self.inner.writer)
+            .field("transfer", &self.inner.transfer)
+            .finish()
+    }
+}
+
+// ===== impl CopyWithBuffer =====
+
+impl<R, W, F> CopyWithBuffer<R, W, F> {
+    /// Calls `f` every time bytes are written to the writer.
+    ///
+    /// The callback receives the total number of bytes copied so far.
+    pub fn progress<G>(self, f: G) -> CopyWithBuffer<R, W, G>
+        where G: FnMut(u64),
+    {
+        CopyWithBuffer {
+            reader: self.reader,
+            writer: self.writer,
+            transfer: self.transfer,
+            progress: f,
+        }
+    }
+
+    /// Returns the number of bytes copied so far.
+    pub fn amount(&self) -> u64 {
+        self.transfer.amt
+    }
+}
+
+impl<R, W, F> Future for CopyWithBuffer<R, W, F>
+    where R: AsyncRead,
+          W: AsyncWrite,
+          F: FnMut(u64),
+{
+    type Item = (u64, R, W, Box<[u8]>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(u64, R, W, Box<[u8]>), io::Error> {
+        let amt = {
+            let reader = self.reader.as_mut().unwrap();
+            let writer = self.writer.as_mut().unwrap();
+            try_ready!(self.transfer.poll(reader, writer, &mut self.progress))
+        };
+
+        // All the data has been written and we've seen EOF, flush out the
+        // data and finish the transfer.
+        try_nb!(self.writer.as_mut().unwrap().flush());
+        let reader = self.reader.take().unwrap();
+        let writer = self.writer.take().unwrap();
+        let buf = self.transfer.take_buffer();
+        Ok((amt, reader, writer, buf).into())
+    }
+}
 
-            // If our buffer has some data, let's write it out!
-            while self.pos < self.cap {
-                let writer = self.writer.as_mut().unwrap();
-                let i = try_nb!(writer.write(&self.buf[self.pos..self.cap]));
-                if i == 0 {
-                    return Err(io::Error::new(io::ErrorKind::WriteZero,
-                                              "write zero byte into writer"));
-                } else {
-                    self.pos += i;
-                    self.amt += i as u64;
-                }
+impl<R: fmt::Debug, W: fmt::Debug, F> fmt::Debug for CopyWithBuffer<R, W, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CopyWithBuffer")
+            .field("reader", &self.reader)
+            .field("writer", &self.writer)
+            .field("transfer", &self.transfer)
+            .finish()
+    }
+}
+
+// ===== impl Transfer =====
+
+pub fn transfer(buf: Box<[u8]>, limit: u64) -> Transfer {
+    Transfer {
+        buf: Buffer {
+            buf: buf,
+            pos: 0,
+            cap: 0,
+            read: 0,
+            limit: limit,
+        },
+        amt: 0,
+        read_done: false,
+    }
+}
+
+impl Transfer {
+    // Copies from `reader` into `writer` until `reader` hits EOF or the limit
+    // is reached, and everything read has been written. The writer is not
+    // flushed. Resolves to the number of bytes copied.
+    pub fn poll<R, W, F>(&mut self, reader: &mut R, writer: &mut W, progress: &mut F)
+        -> Poll<u64, io::Error>
+        where R: AsyncRead,
+              W: AsyncWrite,
+              F: FnMut(u64),
+    {
+        if !self.read_done {
+            let mut reader = Fill {
+                buf: &mut self.buf,
+                reader: reader,
+            };
+            try_ready!(poll_copy_buf(&mut reader, writer, &mut self.amt, progress));
+            self.read_done = true;
+        }
+
+        Ok(Async::Ready(self.amt))
+    }
+
+    // Takes the buffer out, once the transfer is over.
+    pub fn take_buffer(&mut self) -> Box<[u8]> {
+        mem::replace(&mut self.buf.buf, Box::new([]))
+    }
+}
+
+// ===== impl Fill =====
+
+impl<'a, R: AsyncRead> Read for Fill<'a, R> {
+    fn read(&mut self, dst: &mut [u8]) -> io::Result<usize> {
+        let n = match try!(self.poll_fill_buf()) {
+            Async::Ready(src) => {
+                let n = cmp::min(dst.len(), src.len());
+                dst[..n].copy_from_slice(&src[..n]);
+                n
             }
+            Async::NotReady => {
+                return Err(io::Error::new(io::ErrorKind::WouldBlock, "would block"))
+            }
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
 
-            // If we've written al the data and we've seen EOF, flush out the
-            // data and finish the transfer.
-            // done with the entire transfer.
-            if self.pos == self.cap && self.read_done {
-                try_nb!(self.writer.as_mut().unwrap().flush());
-                let reader = self.reader.take().unwrap();
-                let writer = self.writer.take().unwrap();
-                return Ok((self.amt, reader, writer).into())
+impl<'a, R: AsyncRead> AsyncRead for Fill<'a, R> {
+}
+
+impl<'a, R: AsyncRead> AsyncBufRead for Fill<'a, R> {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], io::Error> {
+        let buf = &mut *self.buf;
+
+        // Refill the buffer once it is empty, never asking for more than the
+        // limit allows.
+        if buf.pos == buf.cap {
+            let len = cmp::min(buf.limit - buf.read, buf.buf.len() as u64) as usize;
+            buf.pos = 0;
+            buf.cap = 0;
+            if len > 0 {
+                buf.cap = try_nb!(self.reader.read(&mut buf.buf[..len]));
+                buf.read += buf.cap as u64;
             }
         }
+
+        Ok(Async::Ready(&buf.buf[buf.pos..buf.cap]))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.buf.pos = cmp::min(self.buf.pos + amt, self.buf.cap);
     }
 }
@@ -0,0 +1,112 @@
+use std::io;
+
+use futures::{Async, Future, Poll};
+
+use {AsyncRead, AsyncWrite};
+use copy::{transfer, Transfer};
+
+/// A future which copies data in both directions between two I/O objects.
+///
+/// Created by the [`copy_bidirectional`] function, this future will resolve to
+/// the number of bytes copied in each direction, or an error if one happens.
+///
+/// [`copy_bidirectional`]: fn.copy_bidirectional.html
+#[derive(Debug)]
+pub struct CopyBidirectional<A, B> {
+    a: Option<A>,
+    b: Option<B>,
+    a_to_b: Direction,
+    b_to_a: Direction,
+}
+
+// State of the copy in one direction.
+#[derive(Debug)]
+struct Direction {
+    transfer: Transfer,
+    shut_down: bool,
+}
+
+/// Creates a future which copies all the bytes read from `a` into `b`, and all
+/// the bytes read from `b` into `a`, at the same time.
+///
+/// Once one side hits EOF, the bytes read from it so far are written to and
+/// flushed from the other side, and [`AsyncWrite::shutdown`] is called on the
+/// other side to propagate the half-close. Copying in the opposite direction
+/// carries on undisturbed.
+///
+/// The returned future will only complete once both directions are done, that
+/// is once both `a` and `b` have hit EOF and both have been shut down.
+///
+/// On success the number of bytes copied from `a` to `b` and from `b` to `a`
+/// are returned, along with `a` and `b`. On error the error is returned and the
+/// I/O objects are consumed as well.
+///
+/// [`AsyncWrite::shutdown`]: ../trait.AsyncWrite.html#tymethod.shutdown
+pub fn copy_bidirectional<A, B>(a: A, b: B) -> CopyBidirectional<A, B>
+    where A: AsyncRead + AsyncWrite,
+          B: AsyncRead + AsyncWrite,
+{
+    CopyBidirectional {
+        a: Some(a),
+        b: Some(b),
+        a_to_b: Direction::new(),
+        b_to_a: Direction::new(),
+    }
+}
+
+impl<A, B> Future for CopyBidirectional<A, B>
+    where A: AsyncRead + AsyncWrite,
+          B: AsyncRead + AsyncWrite,
+{
+    type Item = (u64, u64, A, B);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(u64, u64, A, B), io::Error> {
+        let (a_to_b, b_to_a) = {
+            let a = self.a.as_mut().unwrap();
+            let b = self.b.as_mut().unwrap();
+
+            // Always drive both directions, so that one side blocking does not
+            // prevent progress on the other.
+            let a_to_b = try!(self.a_to_b.poll(a, b));
+            let b_to_a = try!(self.b_to_a.poll(b, a));
+            (a_to_b, b_to_a)
+        };
+
+        match (a_to_b, b_to_a) {
+            (Async::Ready(a_to_b), Async::Ready(b_to_a)) => {
+                let a = self.a.take().unwrap();
+                let b = self.b.take().unwrap();
+                Ok((a_to_b, b_to_a, a, b).into())
+            }
+            _ => Ok(Async::NotReady),
+        }
+    }
+}
+
+// ===== impl Direction =====
+
+impl Direction {
+    fn new() -> Direction {
+        Direction {
+            transfer: transfer(Box::new([0; 2048]), u64::max_value()),
+            shut_down: false,
+        }
+    }
+
+    fn poll<R, W>(&mut self, reader: &mut R, writer: &mut W) -> Poll<u64, io::Error>
+        where R: AsyncRead,
+              W: AsyncWrite,
+    {
+        let amt = try_ready!(self.transfer.poll(reader, writer, &mut |_| ()));
+
+        // Once everything has been written and we've seen EOF, pass the
+        // half-close on. Shutting down implies flushing the writer.
+        if !self.shut_down {
+            try_ready!(writer.shutdown());
+            self.shut_down = true;
+        }
+
+        Ok(Async::Ready(amt))
+    }
+}
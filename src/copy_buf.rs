@@ -0,0 +1,104 @@
+use std::io;
+
+use futures::{Async, Future, Poll};
+
+use {AsyncBufRead, AsyncWrite};
+
+/// A future which will copy all data from a buffered reader into a writer.
+///
+/// Created by the [`copy_buf`] function, this future will resolve to the
+/// number of bytes copied or an error if one happens.
+///
+/// [`copy_buf`]: fn.copy_buf.html
+#[derive(Debug)]
+pub struct CopyBuf<R, W> {
+    reader: Option<R>,
+    read_done: bool,
+    writer: Option<W>,
+    amt: u64,
+}
+
+/// Creates a future which represents copying all the bytes from a buffered
+/// reader to a writer.
+///
+/// Unlike [`copy`], this does not allocate a buffer of its own. Bytes are
+/// written straight out of the buffer exposed by `reader` through
+/// [`AsyncBufRead::poll_fill_buf`].
+///
+/// The returned future will only complete once the `reader` has hit EOF and
+/// all bytes have been written to and flushed from the `writer` provided.
+///
+/// On success the number of bytes is returned and the `reader` and `writer`
+/// are consumed. On error the error is returned and the I/O objects are
+/// consumed as well.
+///
+/// [`copy`]: fn.copy.html
+/// [`AsyncBufRead::poll_fill_buf`]: ../trait.AsyncBufRead.html#tymethod.poll_fill_buf
+pub fn copy_buf<R, W>(reader: R, writer: W) -> CopyBuf<R, W>
+    where R: AsyncBufRead,
+          W: AsyncWrite,
+{
+    CopyBuf {
+        reader: Some(reader),
+        read_done: false,
+        writer: Some(writer),
+        amt: 0,
+    }
+}
+
+// Writes out all the bytes of `reader` into `writer` until `reader` hits EOF,
+// without flushing `writer`. `amt` counts the bytes written so far, and
+// `progress` is called with it after every write.
+//
+// This is the loop behind all the copy futures of this crate.
+pub fn poll_copy_buf<R, W, F>(reader: &mut R, writer: &mut W, amt: &mut u64, progress: &mut F)
+    -> Poll<(), io::Error>
+    where R: AsyncBufRead,
+          W: AsyncWrite,
+          F: FnMut(u64),
+{
+    loop {
+        let n = {
+            let buf = try_ready!(reader.poll_fill_buf());
+
+            // An empty buffer means the reader hit EOF.
+            if buf.is_empty() {
+                return Ok(Async::Ready(()));
+            }
+
+            let i = try_nb!(writer.write(buf));
+            if i == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "write zero byte into writer"));
+            }
+            i
+        };
+
+        reader.consume(n);
+        *amt += n as u64;
+        progress(*amt);
+    }
+}
+
+impl<R, W> Future for CopyBuf<R, W>
+    where R: AsyncBufRead,
+          W: AsyncWrite,
+{
+    type Item = (u64, R, W);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(u64, R, W), io::Error> {
+        if !self.read_done {
+            let reader = self.reader.as_mut().unwrap();
+            let writer = self.writer.as_mut().unwrap();
+            try_ready!(poll_copy_buf(reader, writer, &mut self.amt, &mut |_| ()));
+            self.read_done = true;
+        }
+
+        // The reader hit EOF, so flush out the data and finish the transfer.
+        try_nb!(self.writer.as_mut().unwrap().flush());
+        let reader = self.reader.take().unwrap();
+        let writer = self.writer.take().unwrap();
+        Ok((self.amt, reader, writer).into())
+    }
+}
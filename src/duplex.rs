@@ -0,0 +1,189 @@
+use std::cmp;
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex};
+
+use bytes::BytesMut;
+use futures::Poll;
+use futures::task::{self, Task};
+
+use {AsyncRead, AsyncWrite};
+
+/// One end of an in-memory, bidirectional pipe.
+///
+/// Created by the [`duplex`] function. Bytes written to one `DuplexStream`
+/// become readable from its peer, and vice versa.
+///
+/// [`duplex`]: fn.duplex.html
+#[derive(Debug)]
+pub struct DuplexStream {
+    read: Arc<Mutex<Pipe>>,
+    write: Arc<Mutex<Pipe>>,
+}
+
+// A bounded, unidirectional byte buffer shared by a reader and a writer.
+#[derive(Debug)]
+struct Pipe {
+    buf: BytesMut,
+    max_buf_size: usize,
+    is_closed: bool,
+    read_task: Option<Task>,
+    write_task: Option<Task>,
+}
+
+/// Creates a pair of connected in-memory I/O objects.
+///
+/// Each direction is backed by a buffer holding up to `max_buf_size` bytes.
+/// Reading from an empty buffer, or writing to a full one, returns a
+/// "would block" error and arranges for the current task to be notified once
+/// the peer makes progress. Consequently, these operations must happen within
+/// the context of a task when they cannot complete immediately.
+///
+/// Calling `shutdown` on one end, or dropping it, makes the other end read EOF
+/// once the buffered bytes have been consumed. Writing to an end whose peer
+/// was dropped fails with a `BrokenPipe` error.
+///
+/// This is mostly useful for testing code written against `AsyncRead` and
+/// `AsyncWrite` without going through real sockets.
+///
+/// # Panics
+///
+/// This function panics if `max_buf_size` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_io;
+/// # extern crate futures;
+/// # use futures::Future;
+/// # fn main() {
+/// use tokio_io::io::{duplex, read_to_end, write_all, shutdown};
+///
+/// let (client, server) = duplex(64);
+///
+/// let (client, _) = write_all(client, b"ping").wait().unwrap();
+/// shutdown(client).wait().unwrap();
+///
+/// let (_, buf) = read_to_end(server, Vec::new()).wait().unwrap();
+/// assert_eq!(buf, b"ping");
+/// # }
+/// ```
+pub fn duplex(max_buf_size: usize) -> (DuplexStream, DuplexStream) {
+    assert!(max_buf_size > 0, "duplex buffer size must be greater than zero");
+
+    let one = Arc::new(Mutex::new(Pipe::new(max_buf_size)));
+    let two = Arc::new(Mutex::new(Pipe::new(max_buf_size)));
+
+    let a = DuplexStream {
+        read: one.clone(),
+        write: two.clone(),
+    };
+    let b = DuplexStream {
+        read: two,
+        write: one,
+    };
+
+    (a, b)
+}
+
+// ===== impl DuplexStream =====
+
+impl Read for DuplexStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read.lock().unwrap().read(buf)
+    }
+}
+
+impl AsyncRead for DuplexStream {
+}
+
+impl Write for DuplexStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for DuplexStream {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.write.lock().unwrap().close();
+        Ok(().into())
+    }
+}
+
+impl Drop for DuplexStream {
+    fn drop(&mut self) {
+        // Our peer reads EOF, and its writes fail from now on.
+        self.write.lock().unwrap().close();
+        self.read.lock().unwrap().close();
+    }
+}
+
+// ===== impl Pipe =====
+
+impl Pipe {
+    fn new(max_buf_size: usize) -> Pipe {
+        Pipe {
+            buf: BytesMut::with_capacity(max_buf_size),
+            max_buf_size: max_buf_size,
+            is_closed: false,
+            read_task: None,
+            write_task: None,
+        }
+    }
+
+    fn close(&mut self) {
+        self.is_closed = true;
+        if let Some(task) = self.read_task.take() {
+            task.notify();
+        }
+        if let Some(task) = self.write_task.take() {
+            task.notify();
+        }
+    }
+
+    fn read(&mut self, dst: &mut [u8]) -> io::Result<usize> {
+        if self.buf.is_empty() {
+            if self.is_closed || dst.is_empty() {
+                return Ok(0);
+            }
+            self.read_task = Some(task::current());
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        let n = cmp::min(dst.len(), self.buf.len());
+        dst[..n].copy_from_slice(&self.buf.split_to(n));
+
+        if let Some(task) = self.write_task.take() {
+            task.notify();
+        }
+
+        Ok(n)
+    }
+
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        if self.is_closed {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+
+        let avail = self.max_buf_size - self.buf.len();
+        if avail == 0 {
+            if src.is_empty() {
+                return Ok(0);
+            }
+            self.write_task = Some(task::current());
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        let n = cmp::min(src.len(), avail);
+        self.buf.extend_from_slice(&src[..n]);
+
+        if let Some(task) = self.read_task.take() {
+            task.notify();
+        }
+
+        Ok(n)
+    }
+}
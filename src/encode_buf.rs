@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+
+use bytes::{Buf, Bytes, BytesMut};
+use iovec::IoVec;
+
+/// The write buffer of `FramedWrite`, given to `Encoder::encode_to`.
+///
+/// An `EncodeBuf` holds a queue of byte chunks waiting to be written. Encoders
+/// can either write into the last chunk, through `buffer_mut`, or append
+/// `Bytes` values with `put_bytes`, which does not copy them. Chunks are then
+/// written out together with vectored writes.
+///
+/// Small `Bytes` values are copied into the last chunk instead, so that they do
+/// not cost an extra write each.
+#[derive(Debug)]
+pub struct EncodeBuf {
+    // Chunks appended with `put_bytes`, followed by `tail`
+    chunks: VecDeque<Bytes>,
+
+    // Total number of bytes in `chunks`
+    chunks_len: usize,
+
+    // Last chunk, open for writing
+    tail: BytesMut,
+}
+
+// `Bytes` values shorter than this are copied into the last chunk
+const MIN_CHUNK_LEN: usize = 512;
+
+impl EncodeBuf {
+    /// Creates a new, empty `EncodeBuf`, whose last chunk has the given
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> EncodeBuf {
+        EncodeBuf::from(BytesMut::with_capacity(capacity))
+    }
+
+    /// Returns the number of bytes waiting to be written.
+    pub fn len(&self) -> usize {
+        self.chunks_len + self.tail.len()
+    }
+
+    /// Returns true if there are no bytes waiting to be written.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the last chunk of the buffer, to write bytes into.
+    pub fn buffer_mut(&mut self) -> &mut BytesMut {
+        &mut self.tail
+    }
+
+    /// Appends `bytes` to the buffer.
+    ///
+    /// Unless it is small enough to be copied, `bytes` is queued as a chunk of
+    /// its own, without copying it.
+    pub fn put_bytes(&mut self, bytes: Bytes) {
+        if bytes.len() < MIN_CHUNK_LEN {
+            self.tail.extend_from_slice(&bytes);
+            return;
+        }
+
+        if !self.tail.is_empty() {
+            let tail = self.tail.take().freeze();
+            self.push_chunk(tail);
+        }
+
+        self.push_chunk(bytes);
+    }
+
+    /// Consumes the `EncodeBuf`, returning the bytes waiting to be written in
+    /// a single buffer.
+    ///
+    /// This copies the bytes if the buffer holds more than one chunk.
+    pub fn into_bytes_mut(mut self) -> BytesMut {
+        if self.chunks.is_empty() {
+            return self.tail;
+        }
+
+        let mut buf = BytesMut::with_capacity(self.len());
+        for chunk in self.chunks.drain(..) {
+            buf.extend_from_slice(&chunk);
+        }
+        buf.extend_from_slice(&self.tail);
+        buf
+    }
+
+    fn push_chunk(&mut self, chunk: Bytes) {
+        self.chunks_len += chunk.len();
+        self.chunks.push_back(chunk);
+    }
+}
+
+impl From<BytesMut> for EncodeBuf {
+    fn from(buf: BytesMut) -> EncodeBuf {
+        EncodeBuf {
+            chunks: VecDeque::new(),
+            chunks_len: 0,
+            tail: buf,
+        }
+    }
+}
+
+impl Buf for EncodeBuf {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match self.chunks.front() {
+            Some(chunk) => chunk,
+            None => &self.tail,
+        }
+    }
+
+    fn bytes_vec<'a>(&'a self, dst: &mut [&'a IoVec]) -> usize {
+        let chunks = self.chunks.iter()
+            .map(|chunk| &chunk[..])
+            .chain(Some(&self.tail[..]));
+
+        let mut n = 0;
+
+        for chunk in chunks {
+            if n == dst.len() {
+                break;
+            }
+
+            if !chunk.is_empty() {
+                dst[n] = chunk.into();
+                n += 1;
+            }
+        }
+
+        n
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        while cnt > 0 && !self.chunks.is_empty() {
+            let chunk_len = self.chunks[0].len();
+
+            if cnt < chunk_len {
+                self.chunks[0].advance(cnt);
+                self.chunks_len -= cnt;
+                return;
+            }
+
+            self.chunks.pop_front();
+            self.chunks_len -= chunk_len;
+            cnt -= chunk_len;
+        }
+
+        self.tail.advance(cnt);
+    }
+}
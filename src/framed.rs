@@ -2,11 +2,18 @@ use std::io::{self, Read, Write};
 use std::fmt;
 
 use {AsyncRead, AsyncWrite};
-use framed_read::{framed_read2, framed_read2_with_buffer, FramedRead2, Decoder};
-use framed_write::{framed_write2, framed_write2_with_buffer, FramedWrite2, Encoder};
+use encode_buf::EncodeBuf;
+use framed_split::{self, FramedSinkHalf, FramedStreamHalf};
+use framed_read::{framed_read, framed_read2, framed_read2_with_buffer, framed_read2_with_capacity};
+use framed_read::{framed_read2_with_buffer_and_capacity, FramedReadParts};
+use framed_read::{FramedRead, FramedRead2, Decoder};
+use framed_write::{framed_write, framed_write2, framed_write2_with_buffer, framed_write2_with_capacity};
+use framed_write::{framed_write2_with_buffer_and_capacity, FramedWriteParts};
+use framed_write::{FramedWrite, FramedWrite2, Encoder};
 
 use futures::{Stream, Sink, StartSend, Poll};
 use bytes::{BytesMut};
+use iovec::IoVec;
 
 /// A unified `Stream` and `Sink` interface to an underlying I/O object, using
 /// the `Encoder` and `Decoder` traits to encode and decode frames.
@@ -27,6 +34,46 @@ pub fn framed<T, U>(inner: T, codec: U) -> Framed<T, U>
     }
 }
 
+pub fn from_inner<T, U>(inner: FramedRead2<FramedWrite2<Fuse<T, U>>>) -> Framed<T, U> {
+    Framed {
+        inner: inner,
+    }
+}
+
+impl<T, U> Framed<T, U>
+    where T: AsyncRead + AsyncWrite,
+          U: Clone,
+{
+    /// Splits this `Framed` into a `Stream` half and a `Sink` half, which can
+    /// be used independently, e.g. from two different tasks.
+    ///
+    /// The read buffer goes to the `Stream` half and the write buffer to the
+    /// `Sink` half, each half owning a clone of the codec. The two halves can
+    /// be put back together with `FramedStreamHalf::reunite`, keeping both
+    /// buffers.
+    ///
+    /// Unlike `Stream::split`, which is still available on `Framed`, reading
+    /// and writing only share the underlying I/O object, and not the buffers
+    /// and codec of the `Framed`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::{AsyncRead, AsyncWrite};
+    /// use tokio_io::codec::LinesCodec;
+    ///
+    /// # fn split_lines<T: AsyncRead + AsyncWrite>(io: T) {
+    /// let (stream, sink) = io.framed(LinesCodec::new()).into_halves();
+    /// // ... read lines from `stream` and send lines to `sink`
+    /// let (framed, _) = stream.reunite(sink).unwrap();
+    /// # drop(framed);
+    /// # }
+    /// ```
+    pub fn into_halves(self) -> (FramedStreamHalf<T, U>, FramedSinkHalf<T, U>) {
+        framed_split::split(self.inner)
+    }
+}
+
 impl<T, U> Framed<T, U> {
     /// Provides a `Stream` and `Sink` interface for reading and writing to this
     /// `Io` object, using `Decode` and `Encode` to read and write the raw data.
@@ -45,6 +92,9 @@ impl<T, U> Framed<T, U> {
     /// This objects takes a stream and a readbuffer and a writebuffer. These field
     /// can be obtained from an existing `Framed` with the `into_parts` method.
     ///
+    /// The new `Framed` uses the default configuration values, use
+    /// `Builder::from_parts` to configure it.
+    ///
     /// If you want to work more directly with the streams and sink, consider
     /// calling `split` on the `Framed` returned by this method, which will
     /// break them into separate objects, allowing them to interact more easily.
@@ -75,6 +125,18 @@ impl<T, U> Framed<T, U> {
         &mut self.inner.get_mut().get_mut().0
     }
 
+    /// Returns a reference to the read buffer, holding the bytes read but not
+    /// decoded yet.
+    pub fn read_buffer(&self) -> &BytesMut {
+        self.inner.buffer()
+    }
+
+    /// Returns a reference to the write buffer, holding the bytes encoded but
+    /// not written yet.
+    pub fn write_buffer(&self) -> &EncodeBuf {
+        self.inner.get_ref().buffer()
+    }
+
     /// Consumes the `Frame`, returning its underlying I/O stream.
     ///
     /// Note that care should be taken to not tamper with the underlying stream
@@ -111,6 +173,45 @@ impl<T, U> Framed<T, U> {
         let (inner, writebuf) = inner.into_parts();
         (FramedParts { inner: inner.0, readbuf: readbuf, writebuf: writebuf }, inner.1)
     }
+
+    /// Maps the codec of this `Framed` with `f`, keeping the read and write
+    /// buffers.
+    ///
+    /// Bytes read but not decoded yet are decoded by the new codec, and frames
+    /// encoded but not written yet are still written out, before the frames
+    /// encoded by the new codec. This lets protocols which start with a
+    /// handshake, such as HTTP upgrades, switch codecs between two frames.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::{AsyncRead, AsyncWrite};
+    /// use tokio_io::codec::{BytesCodec, LinesCodec};
+    ///
+    /// # fn upgrade<T: AsyncRead + AsyncWrite>(io: T) {
+    /// let lines = io.framed(LinesCodec::new());
+    /// // ... exchange the handshake, then switch to raw bytes
+    /// let bytes = lines.map_codec(|_| BytesCodec::new());
+    /// # drop(bytes);
+    /// # }
+    /// ```
+    pub fn map_codec<C, F>(self, f: F) -> Framed<T, C>
+        where F: FnOnce(U) -> C,
+    {
+        Framed {
+            inner: self.inner.map_inner(|inner| {
+                inner.map_inner(|Fuse(io, codec)| Fuse(io, f(codec)))
+            }),
+        }
+    }
+
+    /// Replaces the codec of this `Framed` with `codec`, keeping the read and
+    /// write buffers.
+    ///
+    /// See `map_codec` for more detail.
+    pub fn replace_codec<C>(self, codec: C) -> Framed<T, C> {
+        self.map_codec(|_| codec)
+    }
 }
 
 impl<T, U> Stream for Framed<T, U>
@@ -173,6 +274,10 @@ impl<T: AsyncRead, U> AsyncRead for Fuse<T, U> {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         self.0.prepare_uninitialized_buffer(buf)
     }
+
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        self.0.read_bufs(bufs)
+    }
 }
 
 impl<T: Write, U> Write for Fuse<T, U> {
@@ -189,6 +294,10 @@ impl<T: AsyncWrite, U> AsyncWrite for Fuse<T, U> {
     fn shutdown(&mut self) -> Poll<(), io::Error> {
         self.0.shutdown()
     }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        self.0.write_bufs(bufs)
+    }
 }
 
 impl<T, U: Decoder> Decoder for Fuse<T, U> {
@@ -202,6 +311,10 @@ impl<T, U: Decoder> Decoder for Fuse<T, U> {
     fn decode_eof(&mut self, buffer: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         self.1.decode_eof(buffer)
     }
+
+    fn resync(&mut self, buffer: &mut BytesMut) -> Result<bool, Self::Error> {
+        self.1.resync(buffer)
+    }
 }
 
 impl<T, U: Encoder> Encoder for Fuse<T, U> {
@@ -211,6 +324,10 @@ impl<T, U: Encoder> Encoder for Fuse<T, U> {
     fn encode(&mut self, item: Self::Item, dst: &mut BytesMut) -> Result<(), Self::Error> {
         self.1.encode(item, dst)
     }
+
+    fn encode_to(&mut self, item: Self::Item, dst: &mut EncodeBuf) -> Result<(), Self::Error> {
+        self.1.encode_to(item, dst)
+    }
 }
 
 /// `FramedParts` contains an export of the data of a Framed transport.
@@ -225,4 +342,208 @@ pub struct FramedParts<T>
     pub readbuf: BytesMut,
     /// A buffer with unprocessed data which are not written yet.
     pub writebuf: BytesMut
-}
\ No newline at end of file
+}
+
+/// Configure the buffers of `Framed`, `FramedRead` and `FramedWrite` values.
+///
+/// By default, the read and write buffers start with a capacity of 8KB, the
+/// read buffer grows as needed to hold a frame, and sends are rejected while
+/// more than 8KB are waiting to be written.
+///
+/// # Examples
+///
+/// ```
+/// # use tokio_io::{AsyncRead, AsyncWrite};
+/// use tokio_io::codec::{Builder, Framed, LinesCodec};
+///
+/// # fn bind_transport<T: AsyncRead + AsyncWrite>(io: T) -> Framed<T, LinesCodec> {
+/// Builder::new()
+///     .read_capacity(1024)
+///     .max_read_buffer_size(64 * 1024)
+///     .write_capacity(1024)
+///     .write_water_marks(1024, 4 * 1024)
+///     .new_framed(io, LinesCodec::new())
+/// # }
+/// # pub fn main() {}
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Builder {
+    // Initial capacity of the read buffer
+    read_capacity: usize,
+
+    // Size of the read buffer from which the decoder needing more data is an
+    // error
+    max_read_buffer_size: usize,
+
+    // Initial capacity of the write buffer
+    write_capacity: usize,
+
+    // Buffered bytes from which sends are rejected
+    high_water_mark: usize,
+
+    // Buffered bytes under which sends are accepted again
+    low_water_mark: usize,
+}
+
+// Default capacity of the buffers, and write backpressure boundary
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+// ===== impl Builder =====
+
+impl Builder {
+    /// Creates a new `Builder` with the default configuration values.
+    pub fn new() -> Builder {
+        Builder {
+            read_capacity: DEFAULT_CAPACITY,
+            max_read_buffer_size: usize::max_value(),
+            write_capacity: DEFAULT_CAPACITY,
+            high_water_mark: DEFAULT_CAPACITY,
+            low_water_mark: DEFAULT_CAPACITY,
+        }
+    }
+
+    /// Sets the initial capacity of the read buffer
+    ///
+    /// The buffer grows as needed to hold a frame. The default value is 8KB.
+    pub fn read_capacity(&mut self, val: usize) -> &mut Self {
+        self.read_capacity = val;
+        self
+    }
+
+    /// Sets the maximum size of the read buffer
+    ///
+    /// When the read buffer holds `val` bytes and the decoder still needs more
+    /// data to decode a frame, an `InvalidData` error is returned instead of
+    /// growing the buffer any further. The buffer can go over `val` by the
+    /// size of one read from the I/O object.
+    ///
+    /// By default, the read buffer grows without bound.
+    pub fn max_read_buffer_size(&mut self, val: usize) -> &mut Self {
+        self.max_read_buffer_size = val;
+        self
+    }
+
+    /// Sets the initial capacity of the write buffer
+    ///
+    /// The default value is 8KB.
+    pub fn write_capacity(&mut self, val: usize) -> &mut Self {
+        self.write_capacity = val;
+        self
+    }
+
+    /// Sets the write buffer water marks used for backpressure
+    ///
+    /// Once `high` bytes are waiting in the write buffer, sends are rejected
+    /// until writing to the I/O object brings the buffer under `low` bytes.
+    /// Both values default to 8KB.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `low` is greater than `high`.
+    pub fn write_water_marks(&mut self, low: usize, high: usize) -> &mut Self {
+        assert!(low <= high, "low water mark above the high water mark");
+        self.low_water_mark = low;
+        self.high_water_mark = high;
+        self
+    }
+
+    /// Creates a configured `Framed` over `io`, using `codec` to encode and
+    /// decode frames.
+    pub fn new_framed<T, U>(&self, io: T, codec: U) -> Framed<T, U>
+        where T: AsyncRead + AsyncWrite,
+              U: Decoder + Encoder,
+    {
+        Framed {
+            inner: self.read2(self.write2(Fuse(io, codec))),
+        }
+    }
+
+    /// Creates a configured `FramedRead` reading from `io`, using `decoder` to
+    /// decode frames.
+    pub fn new_read<T, D>(&self, io: T, decoder: D) -> FramedRead<T, D>
+        where T: AsyncRead,
+              D: Decoder,
+    {
+        framed_read(self.read2(Fuse(io, decoder)))
+    }
+
+    /// Creates a configured `FramedWrite` writing to `io`, using `encoder` to
+    /// encode frames.
+    pub fn new_write<T, E>(&self, io: T, encoder: E) -> FramedWrite<T, E>
+        where T: AsyncWrite,
+              E: Encoder,
+    {
+        framed_write(self.write2(Fuse(io, encoder)))
+    }
+
+    /// Creates a configured `Framed` from the given parts, using `codec` to
+    /// encode and decode frames.
+    ///
+    /// Like `Framed::from_parts`, the bytes in the buffers of `parts` are
+    /// decoded and written out first.
+    pub fn from_parts<T, U>(&self, parts: FramedParts<T>, codec: U) -> Framed<T, U>
+        where T: AsyncRead + AsyncWrite,
+              U: Decoder + Encoder,
+    {
+        let write = self.write2_with_buffer(Fuse(parts.inner, codec), parts.writebuf);
+        Framed {
+            inner: self.read2_with_buffer(write, parts.readbuf),
+        }
+    }
+
+    /// Creates a configured `FramedRead` from the given parts, using `decoder`
+    /// to decode frames.
+    ///
+    /// Like `FramedRead::from_parts`, the bytes in `parts.readbuf` are decoded
+    /// first.
+    pub fn read_from_parts<T, D>(&self, parts: FramedReadParts<T>, decoder: D)
+        -> FramedRead<T, D>
+        where T: AsyncRead,
+              D: Decoder,
+    {
+        framed_read(self.read2_with_buffer(Fuse(parts.inner, decoder), parts.readbuf))
+    }
+
+    /// Creates a configured `FramedWrite` from the given parts, using
+    /// `encoder` to encode frames.
+    ///
+    /// Like `FramedWrite::from_parts`, the bytes in `parts.writebuf` are
+    /// written out first.
+    pub fn write_from_parts<T, E>(&self, parts: FramedWriteParts<T>, encoder: E)
+        -> FramedWrite<T, E>
+        where T: AsyncWrite,
+              E: Encoder,
+    {
+        framed_write(self.write2_with_buffer(Fuse(parts.inner, encoder), parts.writebuf))
+    }
+
+    fn read2<T>(&self, inner: T) -> FramedRead2<T> {
+        let mut inner = framed_read2_with_capacity(inner, self.read_capacity);
+        inner.set_max_buffer_size(self.max_read_buffer_size);
+        inner
+    }
+
+    fn read2_with_buffer<T>(&self, inner: T, buf: BytesMut) -> FramedRead2<T> {
+        let mut inner = framed_read2_with_buffer_and_capacity(inner, buf, self.read_capacity);
+        inner.set_max_buffer_size(self.max_read_buffer_size);
+        inner
+    }
+
+    fn write2<T>(&self, inner: T) -> FramedWrite2<T> {
+        let mut inner = framed_write2_with_capacity(inner, self.write_capacity);
+        inner.set_water_marks(self.low_water_mark, self.high_water_mark);
+        inner
+    }
+
+    fn write2_with_buffer<T>(&self, inner: T, buf: BytesMut) -> FramedWrite2<T> {
+        let mut inner = framed_write2_with_buffer_and_capacity(inner, buf, self.write_capacity);
+        inner.set_water_marks(self.low_water_mark, self.high_water_mark);
+        inner
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
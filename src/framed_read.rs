@@ -1,6 +1,7 @@
 use std::{fmt, io};
 
 use AsyncRead;
+use codec_adapters::{self, AndThen, Map, MapErr, Recover};
 use framed::Fuse;
 
 use futures::{Async, Poll, Stream, Sink, StartSend};
@@ -88,6 +89,111 @@ pub trait Decoder {
             }
         }
     }
+
+    /// Discards bytes up to the next point decoding can resume from, after
+    /// `decode` or `decode_eof` returned an error.
+    ///
+    /// This method is called by the [`Recover`] adapter, and returns `true`
+    /// once `buf` starts at a point decoding can resume from, such as the start
+    /// of the next line of a line based protocol. If `buf` holds no such point
+    /// yet, the bytes that can be skipped are discarded and `false` is
+    /// returned, and this method is called again once more data is read.
+    ///
+    /// The default implementation returns an error, as decoders cannot
+    /// resynchronise in general.
+    ///
+    /// [`Recover`]: struct.Recover.html
+    fn resync(&mut self, buf: &mut BytesMut) -> Result<bool, Self::Error> {
+        let _ = buf;
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "decoder cannot resynchronise after an error").into())
+    }
+
+    /// Keeps decoding after errors, yielding them as items.
+    ///
+    /// When the decoder returns an error, it is yielded as an `Err` item, and
+    /// [`resync`] is then called to skip to the next point decoding can
+    /// resume from. A `FramedRead` or `Framed` using the resulting decoder
+    /// thus keeps yielding frames after an error, instead of terminating.
+    ///
+    /// Errors returned by `resync`, if the decoder does not support
+    /// resynchronising, are returned as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::{Decoder, LinesCodec};
+    ///
+    /// // Yields invalid UTF-8 lines as errors, and goes on with the next line.
+    /// let codec = LinesCodec::new().recover();
+    /// # drop(codec);
+    /// ```
+    ///
+    /// [`resync`]: #method.resync
+    fn recover(self) -> Recover<Self>
+        where Self: Sized,
+    {
+        codec_adapters::recover(self)
+    }
+
+    /// Maps the items decoded by this decoder with `f`.
+    ///
+    /// Encoding, if this codec is also an `Encoder`, is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::{Decoder, LinesCodec};
+    ///
+    /// // Decodes the length of each line.
+    /// let codec = LinesCodec::new().map(|line| line.len());
+    /// # drop(codec);
+    /// ```
+    fn map<F, U>(self, f: F) -> Map<Self, F>
+        where F: FnMut(Self::Item) -> U,
+              Self: Sized,
+    {
+        codec_adapters::map(self, f)
+    }
+
+    /// Maps the errors returned by this codec with `f`.
+    ///
+    /// If this codec is also an `Encoder`, `f` is applied to encoding errors
+    /// as well.
+    fn map_err<F, E>(self, f: F) -> MapErr<Self, F>
+        where F: FnMut(Self::Error) -> E,
+              E: From<io::Error>,
+              Self: Sized,
+    {
+        codec_adapters::map_err(self, f)
+    }
+
+    /// Passes the items decoded by this decoder to `f`, which may fail.
+    ///
+    /// An error returned by `f` is returned by the decoder, and thus ends the
+    /// stream of a `FramedRead`. Encoding, if this codec is also an `Encoder`,
+    /// is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use tokio_io::codec::{Decoder, LinesCodec};
+    ///
+    /// // Decodes one number per line.
+    /// let codec = LinesCodec::new().and_then(|line| {
+    ///     line.parse::<u64>().map_err(|e| {
+    ///         io::Error::new(io::ErrorKind::InvalidData, e)
+    ///     })
+    /// });
+    /// # drop(codec);
+    /// ```
+    fn and_then<F, U>(self, f: F) -> AndThen<Self, F>
+        where F: FnMut(Self::Item) -> Result<U, Self::Error>,
+              Self: Sized,
+    {
+        codec_adapters::and_then(self, f)
+    }
 }
 
 /// A `Stream` of messages decoded from an `AsyncRead`.
@@ -100,6 +206,7 @@ pub struct FramedRead2<T> {
     eof: bool,
     is_readable: bool,
     buffer: BytesMut,
+    max_buffer_size: usize,
 }
 
 const INITIAL_CAPACITY: usize = 8 * 1024;
@@ -157,6 +264,69 @@ impl<T, D> FramedRead<T, D> {
     pub fn decoder_mut(&mut self) -> &mut D {
         &mut self.inner.inner.1
     }
+
+    /// Returns a reference to the read buffer, holding the bytes read but not
+    /// decoded yet.
+    pub fn read_buffer(&self) -> &BytesMut {
+        &self.inner.buffer
+    }
+
+    /// Creates a new `FramedRead` from the given parts, decoding the bytes in
+    /// `parts.readbuf` before reading from `parts.inner`.
+    ///
+    /// This can be used along with `into_parts` to hand the bytes read past a
+    /// handshake over to another `FramedRead`. The new `FramedRead` uses the
+    /// default configuration values, use `Builder::read_from_parts` to
+    /// configure it.
+    pub fn from_parts(parts: FramedReadParts<T>, decoder: D) -> FramedRead<T, D> {
+        FramedRead {
+            inner: framed_read2_with_buffer(Fuse(parts.inner, decoder), parts.readbuf),
+        }
+    }
+
+    /// Consumes the `FramedRead`, returning its underlying I/O stream and the
+    /// bytes read but not decoded yet.
+    ///
+    /// Unlike `into_inner`, this does not lose the bytes already read from the
+    /// underlying I/O stream.
+    pub fn into_parts(self) -> FramedReadParts<T> {
+        let (inner, readbuf) = self.inner.into_parts();
+        FramedReadParts { inner: inner.0, readbuf: readbuf }
+    }
+
+    /// Maps the decoder of this `FramedRead` with `f`, keeping the read
+    /// buffer.
+    ///
+    /// Bytes read but not decoded yet are decoded by the new decoder, so that
+    /// protocols can switch decoders between two frames without losing data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::{BytesCodec, FramedRead, LinesCodec};
+    ///
+    /// # fn upgrade(io: &'static [u8]) {
+    /// let lines = FramedRead::new(io, LinesCodec::new());
+    /// // ... read the handshake, then switch to raw bytes
+    /// let bytes = lines.map_codec(|_| BytesCodec::new());
+    /// # drop(bytes);
+    /// # }
+    /// ```
+    pub fn map_codec<C, F>(self, f: F) -> FramedRead<T, C>
+        where F: FnOnce(D) -> C,
+    {
+        FramedRead {
+            inner: self.inner.map_inner(|Fuse(io, decoder)| Fuse(io, f(decoder))),
+        }
+    }
+
+    /// Replaces the decoder of this `FramedRead` with `decoder`, keeping the
+    /// read buffer.
+    ///
+    /// See `map_codec` for more detail.
+    pub fn replace_codec<C>(self, decoder: C) -> FramedRead<T, C> {
+        self.map_codec(|_| decoder)
+    }
 }
 
 impl<T, D> Stream for FramedRead<T, D>
@@ -208,20 +378,48 @@ impl<T, D> fmt::Debug for FramedRead<T, D>
     }
 }
 
+pub fn framed_read<T, D>(inner: FramedRead2<Fuse<T, D>>) -> FramedRead<T, D> {
+    FramedRead {
+        inner: inner,
+    }
+}
+
+/// `FramedReadParts` contains an export of the data of a `FramedRead`.
+///
+/// It can be used to construct a new `FramedRead` with a different decoder.
+#[derive(Debug)]
+pub struct FramedReadParts<T> {
+    /// The inner transport bytes are read from.
+    pub inner: T,
+    /// The buffer with read but undecoded data.
+    pub readbuf: BytesMut,
+}
+
 // ===== impl FramedRead2 =====
 
 pub fn framed_read2<T>(inner: T) -> FramedRead2<T> {
+    framed_read2_with_capacity(inner, INITIAL_CAPACITY)
+}
+
+pub fn framed_read2_with_capacity<T>(inner: T, capacity: usize) -> FramedRead2<T> {
     FramedRead2 {
         inner: inner,
         eof: false,
         is_readable: false,
-        buffer: BytesMut::with_capacity(INITIAL_CAPACITY),
+        buffer: BytesMut::with_capacity(capacity),
+        max_buffer_size: usize::max_value(),
     }
 }
 
-pub fn framed_read2_with_buffer<T>(inner: T, mut buf: BytesMut) -> FramedRead2<T> {
-    if buf.capacity() < INITIAL_CAPACITY {
-        let bytes_to_reserve = INITIAL_CAPACITY - buf.capacity();
+pub fn framed_read2_with_buffer<T>(inner: T, buf: BytesMut) -> FramedRead2<T> {
+    framed_read2_with_buffer_and_capacity(inner, buf, INITIAL_CAPACITY)
+}
+
+pub fn framed_read2_with_buffer_and_capacity<T>(inner: T, mut buf: BytesMut, capacity: usize)
+    -> FramedRead2<T>
+{
+    if buf.capacity() < capacity {
+        let bytes_to_reserve = capacity - buf.capacity();
         buf.reserve(bytes_to_reserve);
     }
     FramedRead2 {
@@ -229,6 +427,7 @@ pub fn framed_read2_with_buffer<T>(inner: T, mut buf: BytesMut) -> FramedRead2<T
         eof: false,
         is_readable: buf.len() > 0,
         buffer: buf,
+        max_buffer_size: usize::max_value(),
     }
 }
 
@@ -248,6 +447,28 @@ impl<T> FramedRead2<T> {
     pub fn get_mut(&mut self) -> &mut T {
         &mut self.inner
     }
+
+    pub fn buffer(&self) -> &BytesMut {
+        &self.buffer
+    }
+
+    pub fn set_max_buffer_size(&mut self, val: usize) {
+        self.max_buffer_size = val;
+    }
+
+    pub fn map_inner<U, F>(self, f: F) -> FramedRead2<U>
+        where F: FnOnce(T) -> U,
+    {
+        FramedRead2 {
+            inner: f(self.inner),
+            eof: self.eof,
+            // The new decoder may find frames in bytes the old one could not
+            // decode yet
+            is_readable: self.is_readable || !self.buffer.is_empty(),
+            buffer: self.buffer,
+            max_buffer_size: self.max_buffer_size,
+        }
+    }
 }
 
 impl<T> Stream for FramedRead2<T>
@@ -281,6 +502,11 @@ impl<T> Stream for FramedRead2<T>
 
             assert!(!self.eof);
 
+            if self.buffer.len() >= self.max_buffer_size {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "read buffer is full").into());
+            }
+
             // Otherwise, try to read more data and try again. Make sure we've
             // got room for at least one byte to read to ensure that we don't
             // get a spurious 0 that looks like EOF
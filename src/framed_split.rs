@@ -0,0 +1,220 @@
+use std::{error, fmt};
+
+use {AsyncRead, AsyncWrite};
+use encode_buf::EncodeBuf;
+use framed::{Framed, Fuse};
+use framed_read::{FramedRead2, Decoder};
+use framed_write::{FramedWrite2, Encoder};
+use split::{self, ReadHalf, WriteHalf};
+
+use futures::{Stream, Sink, StartSend, Poll};
+use bytes::BytesMut;
+
+/// The `Stream` half of a `Framed` returned from `Framed::into_halves`.
+///
+/// It owns the read buffer of the `Framed` it was split from, along with a
+/// clone of its codec, used for decoding.
+pub struct FramedStreamHalf<T, U> {
+    inner: FramedRead2<Fuse<ReadHalf<T>, U>>,
+}
+
+/// The `Sink` half of a `Framed` returned from `Framed::into_halves`.
+///
+/// It owns the write buffer of the `Framed` it was split from, along with a
+/// clone of its codec, used for encoding.
+pub struct FramedSinkHalf<T, U> {
+    inner: FramedWrite2<Fuse<WriteHalf<T>, U>>,
+}
+
+/// Error indicating a `FramedStreamHalf` and `FramedSinkHalf` were not two
+/// halves of the same `Framed`, and thus could not be reunited.
+///
+/// Both halves are handed back unchanged.
+pub struct ReuniteError<T, U>(pub FramedStreamHalf<T, U>, pub FramedSinkHalf<T, U>);
+
+pub fn split<T, U>(framed: FramedRead2<FramedWrite2<Fuse<T, U>>>)
+    -> (FramedStreamHalf<T, U>, FramedSinkHalf<T, U>)
+    where T: AsyncRead + AsyncWrite,
+          U: Clone,
+{
+    let mut sink = None;
+
+    let stream = framed.map_inner(|inner| {
+        let mut read = None;
+
+        sink = Some(inner.map_inner(|Fuse(io, codec)| {
+            let (r, w) = split::split(io);
+            read = Some(Fuse(r, codec.clone()));
+            Fuse(w, codec)
+        }));
+
+        read.expect("read half")
+    });
+
+    let stream = FramedStreamHalf { inner: stream };
+    let sink = FramedSinkHalf { inner: sink.expect("write half") };
+    (stream, sink)
+}
+
+// ===== impl FramedStreamHalf =====
+
+impl<T, U> FramedStreamHalf<T, U> {
+    /// Returns a reference to the codec used for decoding.
+    pub fn codec(&self) -> &U {
+        &self.inner.get_ref().1
+    }
+
+    /// Returns a mutable reference to the codec used for decoding.
+    pub fn codec_mut(&mut self) -> &mut U {
+        &mut self.inner.get_mut().1
+    }
+
+    /// Returns a reference to the read buffer, holding the bytes read but not
+    /// decoded yet.
+    pub fn read_buffer(&self) -> &BytesMut {
+        self.inner.buffer()
+    }
+
+    /// Checks whether this `FramedStreamHalf` and `other` come from the same
+    /// call to `Framed::into_halves`.
+    pub fn is_pair_of(&self, other: &FramedSinkHalf<T, U>) -> bool {
+        self.inner.get_ref().0.is_pair_of(&other.inner.get_ref().0)
+    }
+
+    /// Reunites this `FramedStreamHalf` with the `FramedSinkHalf` it was split
+    /// from, returning the original `Framed` along with the codec of `other`.
+    ///
+    /// Both the read and the write buffers are kept. The codec of this half,
+    /// used for decoding, becomes the codec of the `Framed`. The codec of
+    /// `other`, used for encoding, is handed back so that any state it built
+    /// up since the split can be carried over.
+    ///
+    /// If the two halves did not come from the same call to
+    /// `Framed::into_halves`, they are handed back in a `ReuniteError`.
+    pub fn reunite(self, other: FramedSinkHalf<T, U>)
+        -> Result<(Framed<T, U>, U), ReuniteError<T, U>>
+    {
+        if !self.is_pair_of(&other) {
+            return Err(ReuniteError(self, other));
+        }
+
+        let sink = other.inner;
+        let mut sink_codec = None;
+        let inner = self.inner.map_inner(|Fuse(r, codec)| {
+            sink.map_inner(|Fuse(w, encoder)| {
+                // The halves are a pair, so this can only succeed.
+                let io = r.reunite(w).ok().expect("halves of the same split");
+                sink_codec = Some(encoder);
+                Fuse(io, codec)
+            })
+        });
+
+        let sink_codec = sink_codec.expect("sink codec");
+        Ok((::framed::from_inner(inner), sink_codec))
+    }
+}
+
+impl<T, U> Stream for FramedStreamHalf<T, U>
+    where T: AsyncRead,
+          U: Decoder,
+{
+    type Item = U::Item;
+    type Error = U::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        self.inner.poll()
+    }
+}
+
+impl<T, U> fmt::Debug for FramedStreamHalf<T, U>
+    where T: fmt::Debug,
+          U: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FramedStreamHalf")
+         .field("io", &self.inner.get_ref().0)
+         .field("codec", &self.inner.get_ref().1)
+         .field("buffer", self.inner.buffer())
+         .finish()
+    }
+}
+
+// ===== impl FramedSinkHalf =====
+
+impl<T, U> FramedSinkHalf<T, U> {
+    /// Returns a reference to the codec used for encoding.
+    pub fn codec(&self) -> &U {
+        &self.inner.get_ref().1
+    }
+
+    /// Returns a mutable reference to the codec used for encoding.
+    pub fn codec_mut(&mut self) -> &mut U {
+        &mut self.inner.get_mut().1
+    }
+
+    /// Returns a reference to the write buffer, holding the bytes encoded but
+    /// not written yet.
+    pub fn write_buffer(&self) -> &EncodeBuf {
+        self.inner.buffer()
+    }
+
+    /// Checks whether this `FramedSinkHalf` and `other` come from the same
+    /// call to `Framed::into_halves`.
+    pub fn is_pair_of(&self, other: &FramedStreamHalf<T, U>) -> bool {
+        other.is_pair_of(self)
+    }
+}
+
+impl<T, U> Sink for FramedSinkHalf<T, U>
+    where T: AsyncWrite,
+          U: Encoder,
+{
+    type SinkItem = U::Item;
+    type SinkError = U::Error;
+
+    fn start_send(&mut self, item: U::Item) -> StartSend<U::Item, U::Error> {
+        self.inner.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
+        self.inner.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), Self::SinkError> {
+        self.inner.close()
+    }
+}
+
+impl<T, U> fmt::Debug for FramedSinkHalf<T, U>
+    where T: fmt::Debug,
+          U: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FramedSinkHalf")
+         .field("io", &self.inner.get_ref().0)
+         .field("codec", &self.inner.get_ref().1)
+         .field("buffer", self.inner.buffer())
+         .finish()
+    }
+}
+
+// ===== impl ReuniteError =====
+
+impl<T, U> fmt::Debug for ReuniteError<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReuniteError")
+            .finish()
+    }
+}
+
+impl<T, U> fmt::Display for ReuniteError<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(error::Error::description(self))
+    }
+}
+
+impl<T, U> error::Error for ReuniteError<T, U> {
+    fn description(&self) -> &str {
+        "tried to reunite framed halves that are not from the same split"
+    }
+}
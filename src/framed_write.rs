@@ -3,10 +3,13 @@ use std::fmt;
 
 use {AsyncRead, AsyncWrite};
 use codec::Decoder;
+use codec_adapters::{self, With};
+use encode_buf::EncodeBuf;
 use framed::Fuse;
 
 use futures::{Async, AsyncSink, Poll, Stream, Sink, StartSend};
 use bytes::BytesMut;
+use iovec::IoVec;
 
 /// Trait of helper objects to write out messages as bytes, for use with
 /// `FramedWrite`.
@@ -27,6 +30,40 @@ pub trait Encoder {
     /// will be written out when possible.
     fn encode(&mut self, item: Self::Item, dst: &mut BytesMut)
               -> Result<(), Self::Error>;
+
+    /// Encodes a frame into the chunk queue provided.
+    ///
+    /// This is the method used by `FramedWrite` and `Framed`. Encoders holding
+    /// large `Bytes` payloads can implement it to append them to `dst` with
+    /// `EncodeBuf::put_bytes`, which does not copy them.
+    ///
+    /// The default implementation calls `encode` with the last chunk of
+    /// `dst`.
+    fn encode_to(&mut self, item: Self::Item, dst: &mut EncodeBuf)
+                 -> Result<(), Self::Error>
+    {
+        self.encode(item, dst.buffer_mut())
+    }
+
+    /// Maps items with `f` before encoding them with this encoder.
+    ///
+    /// Decoding, if this codec is also a `Decoder`, is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::{Encoder, LinesCodec};
+    ///
+    /// // Encodes one number per line.
+    /// let codec = LinesCodec::new().with(|n: u64| n.to_string());
+    /// # drop(codec);
+    /// ```
+    fn with<F, U>(self, f: F) -> With<Self, F, U>
+        where F: FnMut(U) -> Self::Item,
+              Self: Sized,
+    {
+        codec_adapters::with(self, f)
+    }
 }
 
 /// A `Sink` of frames encoded to an `AsyncWrite`.
@@ -36,7 +73,12 @@ pub struct FramedWrite<T, E> {
 
 pub struct FramedWrite2<T> {
     inner: T,
-    buffer: BytesMut,
+    buffer: EncodeBuf,
+    high_water_mark: usize,
+    low_water_mark: usize,
+    // Whether the buffer went over the high water mark, and did not go below
+    // the low water mark since
+    is_full: bool,
 }
 
 const INITIAL_CAPACITY: usize = 8 * 1024;
@@ -93,6 +135,54 @@ impl<T, E> FramedWrite<T, E> {
     pub fn encoder_mut(&mut self) -> &mut E {
         &mut self.inner.inner.1
     }
+
+    /// Returns a reference to the write buffer, holding the bytes encoded but
+    /// not written yet.
+    pub fn write_buffer(&self) -> &EncodeBuf {
+        &self.inner.buffer
+    }
+
+    /// Creates a new `FramedWrite` from the given parts, writing out the bytes
+    /// in `parts.writebuf` before any new frame.
+    ///
+    /// The new `FramedWrite` uses the default configuration values, use
+    /// `Builder::write_from_parts` to configure it.
+    pub fn from_parts(parts: FramedWriteParts<T>, encoder: E) -> FramedWrite<T, E> {
+        FramedWrite {
+            inner: framed_write2_with_buffer(Fuse(parts.inner, encoder), parts.writebuf),
+        }
+    }
+
+    /// Consumes the `FramedWrite`, returning its underlying I/O stream and the
+    /// bytes encoded but not written yet.
+    ///
+    /// Unlike `into_inner`, this does not lose the frames which were not
+    /// flushed yet.
+    pub fn into_parts(self) -> FramedWriteParts<T> {
+        let (inner, writebuf) = self.inner.into_parts();
+        FramedWriteParts { inner: inner.0, writebuf: writebuf }
+    }
+
+    /// Maps the encoder of this `FramedWrite` with `f`, keeping the write
+    /// buffer.
+    ///
+    /// Frames encoded but not written yet are still written out, before the
+    /// frames encoded by the new encoder.
+    pub fn map_codec<C, F>(self, f: F) -> FramedWrite<T, C>
+        where F: FnOnce(E) -> C,
+    {
+        FramedWrite {
+            inner: self.inner.map_inner(|Fuse(io, encoder)| Fuse(io, f(encoder))),
+        }
+    }
+
+    /// Replaces the encoder of this `FramedWrite` with `encoder`, keeping the
+    /// write buffer.
+    ///
+    /// See `map_codec` for more detail.
+    pub fn replace_codec<C>(self, encoder: C) -> FramedWrite<T, C> {
+        self.map_codec(|_| encoder)
+    }
 }
 
 impl<T, E> Sink for FramedWrite<T, E>
@@ -139,23 +229,56 @@ impl<T, U> fmt::Debug for FramedWrite<T, U>
     }
 }
 
+pub fn framed_write<T, E>(inner: FramedWrite2<Fuse<T, E>>) -> FramedWrite<T, E> {
+    FramedWrite {
+        inner: inner,
+    }
+}
+
+/// `FramedWriteParts` contains an export of the data of a `FramedWrite`.
+///
+/// It can be used to construct a new `FramedWrite` with a different encoder.
+#[derive(Debug)]
+pub struct FramedWriteParts<T> {
+    /// The inner transport bytes are written to.
+    pub inner: T,
+    /// A buffer with encoded data which are not written yet.
+    pub writebuf: BytesMut,
+}
+
 // ===== impl FramedWrite2 =====
 
 pub fn framed_write2<T>(inner: T) -> FramedWrite2<T> {
+    framed_write2_with_capacity(inner, INITIAL_CAPACITY)
+}
+
+pub fn framed_write2_with_capacity<T>(inner: T, capacity: usize) -> FramedWrite2<T> {
     FramedWrite2 {
         inner: inner,
-        buffer: BytesMut::with_capacity(INITIAL_CAPACITY),
+        buffer: EncodeBuf::with_capacity(capacity),
+        high_water_mark: BACKPRESSURE_BOUNDARY,
+        low_water_mark: BACKPRESSURE_BOUNDARY,
+        is_full: false,
     }
 }
 
-pub fn framed_write2_with_buffer<T>(inner: T, mut buf: BytesMut) -> FramedWrite2<T> {
-    if buf.capacity() < INITIAL_CAPACITY {
-        let bytes_to_reserve = INITIAL_CAPACITY - buf.capacity();
+pub fn framed_write2_with_buffer<T>(inner: T, buf: BytesMut) -> FramedWrite2<T> {
+    framed_write2_with_buffer_and_capacity(inner, buf, INITIAL_CAPACITY)
+}
+
+pub fn framed_write2_with_buffer_and_capacity<T>(inner: T, mut buf: BytesMut, capacity: usize)
+    -> FramedWrite2<T>
+{
+    if buf.capacity() < capacity {
+        let bytes_to_reserve = capacity - buf.capacity();
         buf.reserve(bytes_to_reserve);
     }
     FramedWrite2 {
         inner: inner,
-        buffer: buf,
+        buffer: EncodeBuf::from(buf),
+        high_water_mark: BACKPRESSURE_BOUNDARY,
+        low_water_mark: BACKPRESSURE_BOUNDARY,
+        is_full: false,
     }
 }
 
@@ -169,12 +292,33 @@ impl<T> FramedWrite2<T> {
     }
 
     pub fn into_parts(self) -> (T, BytesMut) {
-        (self.inner, self.buffer)
+        (self.inner, self.buffer.into_bytes_mut())
     }
 
     pub fn get_mut(&mut self) -> &mut T {
         &mut self.inner
     }
+
+    pub fn buffer(&self) -> &EncodeBuf {
+        &self.buffer
+    }
+
+    pub fn set_water_marks(&mut self, low: usize, high: usize) {
+        self.low_water_mark = low;
+        self.high_water_mark = high;
+    }
+
+    pub fn map_inner<U, F>(self, f: F) -> FramedWrite2<U>
+        where F: FnOnce(T) -> U,
+    {
+        FramedWrite2 {
+            inner: f(self.inner),
+            buffer: self.buffer,
+            high_water_mark: self.high_water_mark,
+            low_water_mark: self.low_water_mark,
+            is_full: self.is_full,
+        }
+    }
 }
 
 impl<T> Sink for FramedWrite2<T>
@@ -184,17 +328,24 @@ impl<T> Sink for FramedWrite2<T>
     type SinkError = T::Error;
 
     fn start_send(&mut self, item: T::Item) -> StartSend<T::Item, T::Error> {
-        // If the buffer is already over 8KiB, then attempt to flush it. If after flushing it's
-        // *still* over 8KiB, then apply backpressure (reject the send).
-        if self.buffer.len() >= BACKPRESSURE_BOUNDARY {
+        // If the buffer is already over the high water mark, then attempt to
+        // flush it. Until flushing brings it under the low water mark, apply
+        // backpressure (reject the send).
+        if self.buffer.len() >= self.high_water_mark {
+            self.is_full = true;
+        }
+
+        if self.is_full {
             try!(self.poll_complete());
 
-            if self.buffer.len() >= BACKPRESSURE_BOUNDARY {
+            if self.buffer.len() >= self.low_water_mark {
                 return Ok(AsyncSink::NotReady(item));
             }
+
+            self.is_full = false;
         }
 
-        try!(self.inner.encode(item, &mut self.buffer));
+        try!(self.inner.encode_to(item, &mut self.buffer));
 
         Ok(AsyncSink::Ready)
     }
@@ -205,16 +356,13 @@ impl<T> Sink for FramedWrite2<T>
         while !self.buffer.is_empty() {
             trace!("writing; remaining={}", self.buffer.len());
 
-            let n = try_nb!(self.inner.write(&self.buffer));
+            // Queued chunks are gathered in a single write when possible
+            let n = try_ready!(self.inner.write_buf(&mut self.buffer));
 
             if n == 0 {
                 return Err(io::Error::new(io::ErrorKind::WriteZero, "failed to
                                           write frame to transport").into());
             }
-
-            // TODO: Add a way to `bytes` to do this w/o returning the drained
-            // data.
-            let _ = self.buffer.split_to(n);
         }
 
         // Try flushing the underlying IO
@@ -241,6 +389,10 @@ impl<T: Decoder> Decoder for FramedWrite2<T> {
     fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T::Item>, T::Error> {
         self.inner.decode_eof(src)
     }
+
+    fn resync(&mut self, src: &mut BytesMut) -> Result<bool, T::Error> {
+        self.inner.resync(src)
+    }
 }
 
 impl<T: Read> Read for FramedWrite2<T> {
@@ -253,4 +405,8 @@ impl<T: AsyncRead> AsyncRead for FramedWrite2<T> {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         self.inner.prepare_uninitialized_buffer(buf)
     }
+
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        self.inner.read_bufs(bufs)
+    }
 }
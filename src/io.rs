@@ -10,7 +10,13 @@
 //! [low level details]: https://tokio.rs/docs/going-deeper-tokio/core-low-level/
 
 pub use allow_std::AllowStdIo;
-pub use copy::{copy, Copy};
+pub use iovec::IoVec;
+pub use buf_reader::BufReader;
+pub use buf_writer::BufWriter;
+pub use copy::{copy, Copy, CopyBuilder, CopyWithBuffer};
+pub use copy_bidirectional::{copy_bidirectional, CopyBidirectional};
+pub use copy_buf::{copy_buf, CopyBuf};
+pub use duplex::{duplex, DuplexStream};
 pub use flush::{flush, Flush};
 pub use lines::{lines, Lines};
 pub use read::{read, Read};
@@ -18,6 +24,23 @@ pub use read_exact::{read_exact, ReadExact};
 pub use read_to_end::{read_to_end, ReadToEnd};
 pub use read_until::{read_until, ReadUntil};
 pub use shutdown::{shutdown, Shutdown};
-pub use split::{ReadHalf, WriteHalf};
+pub use sink_writer::SinkWriter;
+pub use split::{ClosedBy, ReadHalf, ReuniteError, WriteHalf};
+pub use split_ref::{split_arc, split_ref, RefReadHalf, RefWriteHalf};
+pub use stream_reader::StreamReader;
 pub use window::Window;
 pub use write_all::{write_all, WriteAll};
+
+pub mod mock {
+    //! Scripted I/O objects for testing protocol implementations
+    //!
+    //! A [`Builder`] describes, step by step, what the code under test is
+    //! expected to read and write. The resulting [`Mock`] implements
+    //! `AsyncRead` and `AsyncWrite`, plays the script back, and panics as soon
+    //! as the code under test strays from it.
+    //!
+    //! [`Builder`]: struct.Builder.html
+    //! [`Mock`]: struct.Mock.html
+
+    pub use ::mock::{Builder, Mock};
+}
@@ -0,0 +1,159 @@
+use std::io;
+
+use bytes::BytesMut;
+
+use codec::{Decoder, Encoder, EncodeBuf};
+
+/// A codec running an inner codec over the frames of an outer codec.
+///
+/// When decoding, the outer decoder splits the byte stream into frames, and
+/// each frame is then decoded as a whole by the inner decoder, with
+/// `decode_eof`. Every frame must hold exactly one item: an inner decoder that
+/// leaves bytes in a frame, or finds no item in it, fails with an
+/// `InvalidData` error.
+///
+/// When encoding, items are encoded by the inner encoder into a frame of
+/// their own, which is then encoded by the outer encoder.
+///
+/// Errors of the outer codec are converted into the errors of the inner codec.
+///
+/// After an error of the outer decoder, `resync` is forwarded to it. After an
+/// error of the inner decoder, the frame is already consumed, and decoding
+/// resumes right away with the next one.
+///
+/// # Examples
+///
+/// ```
+/// use tokio_io::codec::{LinesCodec, Layered};
+/// use tokio_io::codec::length_delimited::LengthDelimitedCodec;
+///
+/// // Lines of text, each carried in its own length delimited frame.
+/// let codec = Layered::new(LengthDelimitedCodec::new(), LinesCodec::new());
+/// # drop(codec);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Layered<O, I> {
+    outer: O,
+    inner: I,
+
+    // Whether the last error came from the outer decoder
+    outer_failed: bool,
+}
+
+impl<O, I> Layered<O, I> {
+    /// Creates a new `Layered` codec running `inner` over the frames of
+    /// `outer`.
+    pub fn new(outer: O, inner: I) -> Layered<O, I> {
+        Layered {
+            outer: outer,
+            inner: inner,
+            outer_failed: false,
+        }
+    }
+
+    /// Returns a reference to the outer codec.
+    pub fn outer(&self) -> &O {
+        &self.outer
+    }
+
+    /// Returns a mutable reference to the outer codec.
+    pub fn outer_mut(&mut self) -> &mut O {
+        &mut self.outer
+    }
+
+    /// Returns a reference to the inner codec.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner codec.
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    /// Consumes the `Layered` codec, returning the outer and inner codecs.
+    pub fn into_parts(self) -> (O, I) {
+        (self.outer, self.inner)
+    }
+
+    fn decode_frame<E>(&mut self, frame: Result<Option<BytesMut>, E>)
+        -> Result<Option<I::Item>, I::Error>
+        where I: Decoder,
+              I::Error: From<E>,
+    {
+        self.outer_failed = frame.is_err();
+
+        let mut frame = match try!(frame) {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+
+        let item = match try!(self.inner.decode_eof(&mut frame)) {
+            Some(item) => item,
+            None => return Err(invalid_data("frame did not contain an item")),
+        };
+
+        if !frame.is_empty() {
+            return Err(invalid_data("bytes remaining in frame"));
+        }
+
+        Ok(Some(item))
+    }
+}
+
+fn invalid_data<E: From<io::Error>>(msg: &str) -> E {
+    io::Error::new(io::ErrorKind::InvalidData, msg).into()
+}
+
+impl<O, I> Decoder for Layered<O, I>
+    where O: Decoder<Item = BytesMut>,
+          I: Decoder,
+          I::Error: From<O::Error>,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<I::Item>, I::Error> {
+        let frame = self.outer.decode(src);
+        self.decode_frame(frame)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<I::Item>, I::Error> {
+        let frame = self.outer.decode_eof(buf);
+        self.decode_frame(frame)
+    }
+
+    fn resync(&mut self, buf: &mut BytesMut) -> Result<bool, I::Error> {
+        if !self.outer_failed {
+            return Ok(true);
+        }
+
+        let done = try!(self.outer.resync(buf));
+        self.outer_failed = !done;
+        Ok(done)
+    }
+}
+
+impl<O, I> Encoder for Layered<O, I>
+    where O: Encoder,
+          O::Item: From<BytesMut>,
+          I: Encoder,
+          I::Error: From<O::Error>,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    fn encode(&mut self, item: I::Item, dst: &mut BytesMut) -> Result<(), I::Error> {
+        let mut frame = BytesMut::new();
+        try!(self.inner.encode(item, &mut frame));
+        try!(self.outer.encode(frame.into(), dst));
+        Ok(())
+    }
+
+    fn encode_to(&mut self, item: I::Item, dst: &mut EncodeBuf) -> Result<(), I::Error> {
+        let mut frame = BytesMut::new();
+        try!(self.inner.encode(item, &mut frame));
+        try!(self.outer.encode_to(frame.into(), dst));
+        Ok(())
+    }
+}
@@ -1,14 +1,19 @@
 use {codec, AsyncRead, AsyncWrite};
+use checksum::{self, ChecksumBuf};
 
-use bytes::{Buf, BufMut, BytesMut, IntoBuf, BigEndian, LittleEndian};
+use bytes::{Buf, BufMut, Bytes, BytesMut, IntoBuf, BigEndian, LittleEndian};
 use bytes::buf::Chain;
+use iovec::IoVec;
 
 use futures::{Async, AsyncSink, Stream, Sink, StartSend, Poll};
 
 use std::{cmp, fmt};
+use std::collections::VecDeque;
 use std::error::Error as StdError;
 use std::io::{self, Cursor};
 
+pub use checksum::{Checksum, ChecksumMismatch};
+
 /// Configure length delimited `FramedRead`, `FramedWrite`, and `Framed` values.
 ///
 /// `Builder` enables constructing configured length delimited framers. Note
@@ -19,6 +24,9 @@ pub struct Builder {
     // Maximum frame length
     max_frame_len: usize,
 
+    // Maximum frame length when decoding frames piece by piece
+    max_stream_frame_len: usize,
+
     // Number of bytes representing the field length
     length_field_len: usize,
 
@@ -34,8 +42,27 @@ pub struct Builder {
 
     // Length field byte order (little or big endian)
     length_field_is_big_endian: bool,
+
+    // Length field encoded as an unsigned LEB128 varint, instead of a fixed
+    // width integer
+    length_field_is_varint: bool,
+
+    // Checksum trailing each frame, if any
+    checksum: Option<Checksum>,
+
+    // Handling of frames longer than `max_frame_len` when decoding
+    frame_too_big: FrameTooBigPolicy,
+
+    // Maximum number of frames queued for writing
+    max_queued_frames: usize,
+
+    // Number of queued bytes above which no more frames are queued
+    max_queued_bytes: usize,
 }
 
+// Longest LEB128 encoding of a `u64`
+const MAX_VARINT_LEN: usize = 10;
+
 /// Adapts a byte stream into a unified `Stream` and `Sink` that works over
 /// entire frame values.
 ///
@@ -53,7 +80,84 @@ pub struct Framed<T, B: IntoBuf = BytesMut> {
 /// [module level]: index.html
 #[derive(Debug)]
 pub struct FramedRead<T> {
-    inner: codec::FramedRead<T, Decoder>,
+    inner: codec::FramedRead<T, LengthDelimitedCodec>,
+}
+
+/// Adapts a byte stream to a `Stream` yielding entire frame values along with
+/// their frame heads.
+///
+/// Created by `Builder::new_header_read`. See [`Header`] for more detail.
+///
+/// [`Header`]: struct.Header.html
+#[derive(Debug)]
+pub struct FramedHeaderRead<T> {
+    inner: codec::FramedRead<T, HeaderDecoder>,
+}
+
+/// Adapts a byte stream to a `Sink` accepting entire frame values along with
+/// their frame heads.
+///
+/// Created by `Builder::new_header_write`. See [`Header`] for more detail.
+///
+/// [`Header`]: struct.Header.html
+pub struct FramedHeaderWrite<T, B: IntoBuf = BytesMut> {
+    inner: FramedWrite<T, B>,
+}
+
+/// The head of a frame, as handled by `FramedHeaderRead` and
+/// `FramedHeaderWrite`.
+///
+/// The prefix holds the `length_field_offset` bytes found before the length
+/// field, such as type tags, flags or stream IDs. When decoding, they are
+/// split from the rest of the frame without copying, and the length field is
+/// decoded and dropped. The `num_skip` setting is ignored, bytes between the
+/// length field and the payload stay in the payload.
+///
+/// When encoding, the prefix is written before the length field, and must be
+/// exactly `length_field_offset` bytes long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    prefix: BytesMut,
+    length: u64,
+}
+
+/// Adapts a byte stream to a `Stream` yielding frames piece by piece, as
+/// [`FrameEvent`] values.
+///
+/// Created by `Builder::new_stream_read`. Frames are not buffered whole, so
+/// large frames can be passed through with bounded memory.
+///
+/// [`FrameEvent`]: enum.FrameEvent.html
+#[derive(Debug)]
+pub struct FramedStreamRead<T> {
+    inner: codec::FramedRead<T, StreamDecoder>,
+}
+
+/// A piece of a frame, as yielded by `FramedStreamRead`.
+///
+/// Every frame is yielded as a `Start` event, followed by any number of `Data`
+/// events, and an `End` event. A frame skipped for being too big is yielded as
+/// a single `Skipped` event instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameEvent {
+    /// The start of a frame, with the length of its payload, as declared by
+    /// the frame head and adjusted by `length_adjustment`.
+    Start(usize),
+
+    /// A chunk of the payload, as read from the byte stream.
+    Data(BytesMut),
+
+    /// The end of a frame. When a checksum is configured, the trailer has been
+    /// verified.
+    End,
+
+    /// A frame longer than the max frame length, with the length of its
+    /// payload as declared by the frame head and adjusted by
+    /// `length_adjustment`.
+    ///
+    /// Only yielded with `FrameTooBigPolicy::Skip`. The bytes of the frame are
+    /// discarded as they come in, before the next event is yielded.
+    Skipped(u64),
 }
 
 /// An error when the number of bytes read is more than max frame length.
@@ -61,19 +165,97 @@ pub struct FrameTooBig {
     _priv: (),
 }
 
-#[derive(Debug)]
-struct Decoder {
+/// A codec for frames delimited by a frame head specifying their lengths.
+///
+/// This allows the consumer to work with entire frames without having to worry
+/// about buffering or other framing logic, using the generic `codec` types
+/// such as `codec::Framed`. It decodes frames into `BytesMut` values and
+/// encodes frames from `Bytes` values.
+///
+/// See [module level] documentation for more detail.
+///
+/// [module level]: index.html
+#[derive(Debug, Clone)]
+pub struct LengthDelimitedCodec {
     // Configuration values
     builder: Builder,
 
     // Read state
     state: DecodeState,
+
+    // Head of the frame being read, when the head is kept
+    header: Option<Header>,
+
+    // Number of frames read, to report checksum mismatches
+    frames: u64,
+
+    // Number of frames skipped for being too big
+    skipped: u64,
+
+    // Where decoding can resume from after an error
+    resync: Resync,
 }
 
 #[derive(Debug, Clone, Copy)]
 enum DecodeState {
     Head,
     Data(usize),
+    Skip(u64),
+}
+
+// Where decoding can resume from after an error.
+#[derive(Debug, Clone, Copy)]
+enum Resync {
+    // The start of the buffer
+    Ready,
+
+    // Past the given number of bytes, the rest of the frame that failed
+    Skip(u64),
+
+    // Past all the bytes buffered, at the end of the stream
+    Discard,
+
+    // Nowhere, the frame boundaries are lost
+    Lost,
+}
+
+// What a frame head says about the frame that follows.
+#[derive(Debug, Clone, Copy)]
+enum FrameHead {
+    // A frame to read, with the length of its payload
+    Data(usize),
+
+    // A frame too big to read, being skipped, with the length of its payload
+    TooBig(u64),
+}
+
+/// What to do when decoding a frame longer than the max frame length.
+///
+/// See `Builder::on_frame_too_big` for more detail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameTooBigPolicy {
+    /// Return a `FrameTooBig` error, the stream of frames cannot be read any
+    /// further.
+    Error,
+
+    /// Discard the frame and go on with the next one, counting it in
+    /// `LengthDelimitedCodec::skipped_frames`.
+    Skip,
+}
+
+// Decodes frames along with their heads.
+#[derive(Debug)]
+struct HeaderDecoder {
+    codec: LengthDelimitedCodec,
+}
+
+// Decodes frames piece by piece.
+#[derive(Debug)]
+struct StreamDecoder {
+    codec: LengthDelimitedCodec,
+
+    // Running checksum of the payload read so far
+    crc: u32,
 }
 
 /// Adapts a byte stream to a `Sink` accepting entire frame values.
@@ -88,8 +270,17 @@ pub struct FramedWrite<T, B: IntoBuf = BytesMut> {
     // Configuration values
     builder: Builder,
 
-    // Current frame being written
-    frame: Option<Chain<Cursor<BytesMut>, B::Buf>>,
+    // Frames waiting to be written
+    queue: FrameQueue<Chain<Cursor<BytesMut>, ChecksumBuf<B::Buf>>>,
+}
+
+// Frames waiting to be written, in order, as a single `Buf`.
+#[derive(Debug)]
+struct FrameQueue<B> {
+    frames: VecDeque<B>,
+
+    // Total number of bytes left in `frames`
+    remaining: usize,
 }
 
 // ===== impl Framed =====
@@ -102,6 +293,11 @@ impl<T: AsyncRead + AsyncWrite, B: IntoBuf> Framed<T, B> {
 }
 
 impl<T, B: IntoBuf> Framed<T, B> {
+    /// Returns a reference to the codec used for decoding.
+    pub fn decoder(&self) -> &LengthDelimitedCodec {
+        self.inner.decoder()
+    }
+
     /// Returns a reference to the underlying I/O stream wrapped by `Framed`.
     ///
     /// Note that care should be taken to not tamper with the underlying stream
@@ -196,6 +392,11 @@ impl<T> FramedRead<T> {
         self.inner.decoder_mut().builder.max_frame_length(val);
     }
 
+    /// Returns a reference to the codec used for decoding.
+    pub fn decoder(&self) -> &LengthDelimitedCodec {
+        self.inner.decoder()
+    }
+
     /// Returns a reference to the underlying I/O stream wrapped by `FramedRead`.
     ///
     /// Note that care should be taken to not tamper with the underlying stream
@@ -269,103 +470,278 @@ impl<T: AsyncWrite> AsyncWrite for FramedRead<T> {
     fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
         self.inner.get_mut().write_buf(buf)
     }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        self.inner.get_mut().write_bufs(bufs)
+    }
 }
 
-// ===== impl Decoder ======
+// ===== impl LengthDelimitedCodec ======
+
+impl LengthDelimitedCodec {
+    /// Creates a new `LengthDelimitedCodec` with default configuration values.
+    pub fn new() -> LengthDelimitedCodec {
+        Builder::new().new_codec()
+    }
 
-impl Decoder {
-    fn decode_head(&mut self, src: &mut BytesMut) -> io::Result<Option<usize>> {
-        let head_len = self.builder.num_head_bytes();
-        let field_len = self.builder.length_field_len;
+    /// Returns the current max frame setting
+    ///
+    /// This is the largest size this codec will accept from the wire or
+    /// encode. Larger frames will be rejected.
+    pub fn max_frame_length(&self) -> usize {
+        self.builder.max_frame_len
+    }
 
-        if src.len() < head_len {
+    /// Updates the max frame setting.
+    ///
+    /// The change takes effect the next time a frame is decoded or encoded.
+    /// In other words, if a frame is currently in process of being decoded
+    /// with a frame size greater than `val` but less than the max frame length
+    /// in effect before calling this function, then the frame will be allowed.
+    pub fn set_max_frame_length(&mut self, val: usize) {
+        self.builder.max_frame_length(val);
+    }
+
+    /// Returns the number of frames skipped for being longer than the max
+    /// frame length.
+    ///
+    /// See `Builder::on_frame_too_big` for more detail.
+    pub fn skipped_frames(&self) -> u64 {
+        self.skipped
+    }
+
+    fn decode_head(&mut self, src: &mut BytesMut, keep_header: bool, max_len: usize)
+        -> io::Result<Option<FrameHead>>
+    {
+        self.resync = Resync::Ready;
+
+        let offset = self.builder.length_field_offset;
+
+        if src.len() < offset {
             // Not enough data
             return Ok(None);
         }
 
-        let n = {
-            let mut src = Cursor::new(&mut *src);
+        let (n, field_len) = match self.builder.decode_length_field(&src[offset..]) {
+            Ok(Some(field)) => field,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.resync = Resync::Lost;
+                return Err(e);
+            }
+        };
 
-            // Skip the required bytes
-            src.advance(self.builder.length_field_offset);
+        let num_skip = if keep_header {
+            offset + field_len
+        } else {
+            self.builder.num_skip.unwrap_or(offset + field_len)
+        };
+        let head_len = cmp::max(offset + field_len, num_skip);
 
-            // match endianess
-            let n = if self.builder.length_field_is_big_endian {
-                src.get_uint::<BigEndian>(field_len)
+        if src.len() < head_len {
+            // Not enough data
+            return Ok(None);
+        }
+
+        let raw_len = n;
+
+        if n > max_len as u64 {
+            let n = if self.builder.length_adjustment < 0 {
+                n.checked_sub(-self.builder.length_adjustment as u64)
             } else {
-                src.get_uint::<LittleEndian>(field_len)
+                n.checked_add(self.builder.length_adjustment as u64)
             };
 
-            if n > self.builder.max_frame_len as u64 {
+            if self.builder.frame_too_big == FrameTooBigPolicy::Error {
+                // The frame can be skipped when resynchronising, as long as
+                // its length makes sense
+                self.resync = match n {
+                    Some(n) => {
+                        let trailer_len = self.builder.trailer_len() as u64;
+                        Resync::Skip((num_skip as u64).saturating_add(n).saturating_add(trailer_len))
+                    }
+                    None => Resync::Lost,
+                };
+
                 return Err(io::Error::new(io::ErrorKind::InvalidData, FrameTooBig {
                     _priv: (),
                 }));
             }
 
-            // The check above ensures there is no overflow
-            let n = n as usize;
-
-            // Adjust `n` with bounds checking
-            let n = if self.builder.length_adjustment < 0 {
-                n.checked_sub(-self.builder.length_adjustment as usize)
-            } else {
-                n.checked_add(self.builder.length_adjustment as usize)
+            let n = match n {
+                Some(n) => n,
+                None => {
+                    self.resync = Resync::Lost;
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, "provided length would overflow after adjustment"));
+                }
             };
 
-            // Error handling
-            match n {
-                Some(n) => n,
-                None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "provided length would overflow after adjustment")),
-            }
+            // Discard the frame as its bytes come in
+            let _ = src.split_to(num_skip);
+            self.state = DecodeState::Skip(n.saturating_add(self.builder.trailer_len() as u64));
+            self.frames += 1;
+            self.skipped += 1;
+
+            return Ok(Some(FrameHead::TooBig(n)));
+        }
+
+        // The check above ensures there is no overflow
+        let n = n as usize;
+
+        // Adjust `n` with bounds checking
+        let n = if self.builder.length_adjustment < 0 {
+            n.checked_sub(-self.builder.length_adjustment as usize)
+        } else {
+            n.checked_add(self.builder.length_adjustment as usize)
         };
 
-        let num_skip = self.builder.get_num_skip();
+        // Error handling
+        let n = match n {
+            Some(n) => n,
+            None => {
+                self.resync = Resync::Lost;
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "provided length would overflow after adjustment"));
+            }
+        };
 
-        if num_skip > 0 {
+        if keep_header {
+            let prefix = src.split_to(offset);
+            let _ = src.split_to(field_len);
+            self.header = Some(Header {
+                prefix: prefix,
+                length: raw_len,
+            });
+        } else if num_skip > 0 {
             let _ = src.split_to(num_skip);
         }
 
-        // Ensure that the buffer has enough space to read the incoming
-        // payload
-        src.reserve(n);
-
-        return Ok(Some(n));
+        Ok(Some(FrameHead::Data(n)))
     }
 
-    fn decode_data(&self, n: usize, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+    fn decode_data(&mut self, n: usize, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
         // At this point, the buffer has already had the required capacity
         // reserved. All there is to do is read.
-        if src.len() < n {
+        if src.len() < n + self.builder.trailer_len() {
             return Ok(None);
         }
 
-        Ok(Some(src.split_to(n)))
+        let data = src.split_to(n);
+        let actual = self.builder.checksum.map(|checksum| checksum.compute(&data));
+
+        try!(self.decode_trailer(actual, src));
+
+        Ok(Some(data))
     }
-}
 
-impl codec::Decoder for Decoder {
-    type Item = BytesMut;
-    type Error = io::Error;
+    // Finishes reading a frame, checking the trailer against the checksum
+    // computed from the frame contents, if any.
+    fn decode_trailer(&mut self, actual: Option<u32>, src: &mut BytesMut) -> io::Result<()> {
+        let frame = self.frames;
+        self.frames += 1;
 
-    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
-        let n = match self.state {
-            DecodeState::Head => {
-                match try!(self.decode_head(src)) {
-                    Some(n) => {
-                        self.state = DecodeState::Data(n);
-                        n
+        // The frame is consumed even on a checksum mismatch, so the next one
+        // can still be read.
+        self.state = DecodeState::Head;
+
+        if let Some(actual) = actual {
+            let trailer = src.split_to(checksum::TRAILER_LEN);
+            let expected = checksum::decode_trailer(&trailer,
+                                                    self.builder.length_field_is_big_endian);
+
+            if expected != actual {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          checksum::mismatch(frame, expected, actual)));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Discards the bytes of a frame being skipped, returning whether all of
+    // them are gone.
+    fn skip_frame(&mut self, src: &mut BytesMut) -> bool {
+        if let DecodeState::Skip(n) = self.state {
+            let len = cmp::min(n, src.len() as u64);
+            let _ = src.split_to(len as usize);
+
+            if len < n {
+                self.state = DecodeState::Skip(n - len);
+                return false;
+            }
+
+            self.state = DecodeState::Head;
+        }
+
+        true
+    }
+
+    // Checks that no bytes are left at the end of the stream, once no more
+    // frames can be decoded.
+    fn decode_end(&mut self, src: &BytesMut) -> io::Result<()> {
+        if src.is_empty() {
+            return Ok(());
+        }
+
+        self.resync = Resync::Discard;
+        Err(io::Error::new(io::ErrorKind::Other, "bytes remaining on stream"))
+    }
+
+    // Discards the bytes up to the next frame head after an error, returning
+    // whether all of them are gone.
+    fn resync_frame(&mut self, src: &mut BytesMut) -> io::Result<bool> {
+        match self.resync {
+            Resync::Ready => {}
+            Resync::Skip(n) => {
+                self.state = DecodeState::Skip(n);
+                self.frames += 1;
+            }
+            Resync::Discard => {
+                src.clear();
+                self.state = DecodeState::Head;
+            }
+            Resync::Lost => {
+                return Err(io::Error::new(io::ErrorKind::Other,
+                                          "cannot find the next frame after a malformed frame head"));
+            }
+        }
+
+        self.resync = Resync::Ready;
+        Ok(self.skip_frame(src))
+    }
+
+    fn decode_frame(&mut self, src: &mut BytesMut, keep_header: bool)
+        -> io::Result<Option<BytesMut>>
+    {
+        let n = loop {
+            if !self.skip_frame(src) {
+                return Ok(None);
+            }
+
+            match self.state {
+                DecodeState::Head => {
+                    let max_len = self.builder.max_frame_len;
+                    match try!(self.decode_head(src, keep_header, max_len)) {
+                        Some(FrameHead::Data(n)) => {
+                            self.state = DecodeState::Data(n);
+
+                            // Ensure that the buffer has enough space to read
+                            // the incoming payload
+                            src.reserve(n + self.builder.trailer_len());
+
+                            break n;
+                        }
+                        // Go on with the next head once the frame is skipped
+                        Some(FrameHead::TooBig(_)) => continue,
+                        None => return Ok(None),
                     }
-                    None => return Ok(None),
                 }
+                DecodeState::Data(n) => break n,
+                DecodeState::Skip(_) => unreachable!(),
             }
-            DecodeState::Data(n) => n,
         };
 
         match try!(self.decode_data(n, src)) {
             Some(data) => {
-                // Update the decode state
-                self.state = DecodeState::Head;
-
                 // Make sure the buffer has enough space to read the next head
                 src.reserve(self.builder.num_head_bytes());
 
@@ -376,6 +752,189 @@ impl codec::Decoder for Decoder {
     }
 }
 
+impl codec::Decoder for LengthDelimitedCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        self.decode_frame(src, false)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        match try!(self.decode(src)) {
+            Some(frame) => Ok(Some(frame)),
+            None => {
+                try!(self.decode_end(src));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Skips the rest of a frame longer than the max frame length, and any
+    /// byte left at the end of the stream.
+    ///
+    /// A frame failing its checksum is already consumed, decoding resumes with
+    /// the next one. After a malformed frame head, such as an invalid varint or
+    /// a length overflowing once adjusted, the frame boundaries are lost and an
+    /// error is returned.
+    fn resync(&mut self, src: &mut BytesMut) -> io::Result<bool> {
+        self.resync_frame(src)
+    }
+}
+
+impl codec::Encoder for LengthDelimitedCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, data: Bytes, dst: &mut BytesMut) -> io::Result<()> {
+        dst.reserve(self.builder.length_field_len + data.len() + self.builder.trailer_len());
+        try!(self.builder.encode_head(data.len(), dst));
+
+        let trailer = self.builder.checksum.map(|checksum| {
+            checksum::encode_trailer(checksum.compute(&data),
+                                     self.builder.length_field_is_big_endian)
+        });
+
+        dst.put(data);
+
+        if let Some(trailer) = trailer {
+            dst.put_slice(&trailer);
+        }
+
+        Ok(())
+    }
+
+    fn encode_to(&mut self, data: Bytes, dst: &mut codec::EncodeBuf) -> io::Result<()> {
+        try!(self.builder.encode_head(data.len(), dst.buffer_mut()));
+
+        let trailer = self.builder.checksum.map(|checksum| {
+            checksum::encode_trailer(checksum.compute(&data),
+                                     self.builder.length_field_is_big_endian)
+        });
+
+        dst.put_bytes(data);
+
+        if let Some(trailer) = trailer {
+            dst.buffer_mut().extend_from_slice(&trailer);
+        }
+
+        Ok(())
+    }
+}
+
+// ===== impl HeaderDecoder =====
+
+impl codec::Decoder for HeaderDecoder {
+    type Item = (Header, BytesMut);
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<(Header, BytesMut)>> {
+        match try!(self.codec.decode_frame(src, true)) {
+            Some(data) => {
+                let header = self.codec.header.take().expect("frame without header");
+                Ok(Some((header, data)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<(Header, BytesMut)>> {
+        match try!(self.decode(src)) {
+            Some(frame) => Ok(Some(frame)),
+            None => {
+                try!(self.codec.decode_end(src));
+                Ok(None)
+            }
+        }
+    }
+
+    fn resync(&mut self, src: &mut BytesMut) -> io::Result<bool> {
+        self.codec.resync_frame(src)
+    }
+}
+
+// ===== impl StreamDecoder =====
+
+impl codec::Decoder for StreamDecoder {
+    type Item = FrameEvent;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<FrameEvent>> {
+        if !self.codec.skip_frame(src) {
+            return Ok(None);
+        }
+
+        match self.codec.state {
+            DecodeState::Head => {
+                let max_len = self.codec.builder.max_stream_frame_len;
+                match try!(self.codec.decode_head(src, false, max_len)) {
+                    Some(FrameHead::Data(n)) => {
+                        self.codec.state = DecodeState::Data(n);
+                        self.crc = !0;
+                        Ok(Some(FrameEvent::Start(n)))
+                    }
+                    Some(FrameHead::TooBig(n)) => Ok(Some(FrameEvent::Skipped(n))),
+                    None => Ok(None),
+                }
+            }
+            DecodeState::Data(0) => {
+                if src.len() < self.codec.builder.trailer_len() {
+                    return Ok(None);
+                }
+
+                let actual = self.codec.builder.checksum.map(|_| !self.crc);
+                try!(self.codec.decode_trailer(actual, src));
+
+                // Make sure the buffer has enough space to read the next head
+                src.reserve(self.codec.builder.num_head_bytes());
+
+                Ok(Some(FrameEvent::End))
+            }
+            DecodeState::Data(n) => {
+                if src.is_empty() {
+                    return Ok(None);
+                }
+
+                let data = src.split_to(cmp::min(n, src.len()));
+                self.codec.state = DecodeState::Data(n - data.len());
+
+                if let Some(checksum) = self.codec.builder.checksum {
+                    self.crc = checksum::update(checksum, self.crc, &data);
+                }
+
+                Ok(Some(FrameEvent::Data(data)))
+            }
+            DecodeState::Skip(_) => unreachable!(),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<FrameEvent>> {
+        match try!(self.decode(src)) {
+            Some(event) => Ok(Some(event)),
+            None => {
+                if let DecodeState::Data(_) = self.codec.state {
+                    self.codec.resync = Resync::Discard;
+                    return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                              "stream ended in the middle of a frame"));
+                }
+
+                try!(self.codec.decode_end(src));
+                Ok(None)
+            }
+        }
+    }
+
+    fn resync(&mut self, src: &mut BytesMut) -> io::Result<bool> {
+        self.codec.resync_frame(src)
+    }
+}
+
+impl Default for LengthDelimitedCodec {
+    fn default() -> LengthDelimitedCodec {
+        LengthDelimitedCodec::new()
+    }
+}
+
 // ===== impl FramedWrite =====
 
 impl<T: AsyncWrite, B: IntoBuf> FramedWrite<T, B> {
@@ -435,58 +994,45 @@ impl<T, B: IntoBuf> FramedWrite<T, B> {
 }
 
 impl<T: AsyncWrite, B: IntoBuf> FramedWrite<T, B> {
-    // If there is a buffered frame, try to write it to `T`
+    // If there are queued frames, try to write them all to `T`
     fn do_write(&mut self) -> Poll<(), io::Error> {
-        if self.frame.is_none() {
-            return Ok(Async::Ready(()));
+        while self.queue.has_remaining() {
+            try_ready!(self.write_queue());
         }
 
-        loop {
-            let frame = self.frame.as_mut().unwrap();
-            try_ready!(self.inner.write_buf(frame));
-
-            if !frame.has_remaining() {
-                break;
-            }
-        }
-
-        self.frame = None;
-
         Ok(Async::Ready(()))
     }
 
-    fn set_frame(&mut self, buf: B::Buf) -> io::Result<()> {
-        let mut head = BytesMut::with_capacity(8);
-        let n = buf.remaining();
-
-        if n > self.builder.max_frame_len {
-            return Err(io::Error::new(io::ErrorKind::InvalidInput, FrameTooBig {
-                _priv: (),
-            }));
+    // Writes queued frames to `T` until another frame can be queued
+    fn poll_room(&mut self) -> Poll<(), io::Error> {
+        while !self.queue.has_room(&self.builder) {
+            try_ready!(self.write_queue());
         }
 
-        // Adjust `n` with bounds checking
-        let n = if self.builder.length_adjustment < 0 {
-            n.checked_add(-self.builder.length_adjustment as usize)
-        } else {
-            n.checked_sub(self.builder.length_adjustment as usize)
-        };
+        Ok(Async::Ready(()))
+    }
 
-        // Error handling
-        let n = match n {
-            Some(n) => n,
-            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "provided length would overflow after adjustment")),
-        };
+    fn write_queue(&mut self) -> Poll<(), io::Error> {
+        // As many queued frames as possible are gathered in a single write
+        let n = try_ready!(self.inner.write_buf(&mut self.queue));
 
-        if self.builder.length_field_is_big_endian {
-            head.put_uint::<BigEndian>(n as u64, self.builder.length_field_len);
-        } else {
-            head.put_uint::<LittleEndian>(n as u64, self.builder.length_field_len);
+        if n == 0 {
+            return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                      "failed to write frame to transport"));
         }
 
-        debug_assert!(self.frame.is_none());
+        Ok(Async::Ready(()))
+    }
+
+    fn set_frame(&mut self, prefix: &[u8], buf: B::Buf) -> io::Result<()> {
+        let mut head = BytesMut::with_capacity(prefix.len() + 8);
+        head.put_slice(prefix);
+        try!(self.builder.encode_head(buf.remaining(), &mut head));
 
-        self.frame = Some(head.into_buf().chain(buf));
+        let buf = checksum::checksum_buf(buf,
+                                         self.builder.checksum,
+                                         self.builder.length_field_is_big_endian);
+        self.queue.push(head.into_buf().chain(buf));
 
         Ok(())
     }
@@ -497,17 +1043,17 @@ impl<T: AsyncWrite, B: IntoBuf> Sink for FramedWrite<T, B> {
     type SinkError = io::Error;
 
     fn start_send(&mut self, item: B) -> StartSend<B, io::Error> {
-        if !try!(self.do_write()).is_ready() {
+        if !try!(self.poll_room()).is_ready() {
             return Ok(AsyncSink::NotReady(item));
         }
 
-        try!(self.set_frame(item.into_buf()));
+        try!(self.set_frame(&[], item.into_buf()));
 
         Ok(AsyncSink::Ready)
     }
 
     fn poll_complete(&mut self) -> Poll<(), io::Error> {
-        // Write any buffered frame to T
+        // Write any queued frames to T
         try_ready!(self.do_write());
 
         // Try flushing the underlying IO
@@ -545,6 +1091,10 @@ impl<T: AsyncRead, U: IntoBuf> AsyncRead for FramedWrite<T, U> {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         self.get_ref().prepare_uninitialized_buffer(buf)
     }
+
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        self.get_mut().read_bufs(bufs)
+    }
 }
 
 impl<T, B: IntoBuf> fmt::Debug for FramedWrite<T, B>
@@ -555,11 +1105,282 @@ impl<T, B: IntoBuf> fmt::Debug for FramedWrite<T, B>
         f.debug_struct("FramedWrite")
             .field("inner", &self.inner)
             .field("builder", &self.builder)
-            .field("frame", &self.frame)
+            .field("queue", &self.queue)
+            .finish()
+    }
+}
+
+// ===== impl FrameQueue =====
+
+impl<B: Buf> FrameQueue<B> {
+    fn has_room(&self, builder: &Builder) -> bool {
+        // A frame can always be queued once the previous ones are written
+        self.frames.is_empty() ||
+            (self.frames.len() < builder.max_queued_frames &&
+             self.remaining < builder.max_queued_bytes)
+    }
+
+    fn push(&mut self, frame: B) {
+        if frame.has_remaining() {
+            self.remaining += frame.remaining();
+            self.frames.push_back(frame);
+        }
+    }
+}
+
+impl<B: Buf> Buf for FrameQueue<B> {
+    fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match self.frames.front() {
+            Some(frame) => frame.bytes(),
+            None => &[],
+        }
+    }
+
+    fn bytes_vec<'a>(&'a self, dst: &mut [&'a IoVec]) -> usize {
+        let mut n = 0;
+
+        for frame in &self.frames {
+            if n == dst.len() {
+                break;
+            }
+
+            let cnt = frame.bytes_vec(&mut dst[n..]);
+            let len = dst[n..n + cnt].iter().fold(0, |len, buf| len + buf.len());
+            n += cnt;
+
+            // Chunks of the next frame can only follow a complete frame
+            if len < frame.remaining() {
+                break;
+            }
+        }
+
+        n
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        assert!(cnt <= self.remaining);
+        self.remaining -= cnt;
+
+        while cnt > 0 {
+            let frame_len = self.frames[0].remaining();
+
+            if cnt < frame_len {
+                self.frames[0].advance(cnt);
+                break;
+            }
+
+            self.frames.pop_front();
+            cnt -= frame_len;
+        }
+    }
+}
+
+// ===== impl FramedHeaderRead =====
+
+impl<T> FramedHeaderRead<T> {
+    /// Returns a reference to the codec used for decoding.
+    pub fn decoder(&self) -> &LengthDelimitedCodec {
+        &self.inner.decoder().codec
+    }
+
+    /// Returns a reference to the underlying I/O stream wrapped by
+    /// `FramedHeaderRead`.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise
+    /// being worked with.
+    pub fn get_ref(&self) -> &T {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying I/O stream wrapped by
+    /// `FramedHeaderRead`.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise being
+    /// worked with.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+
+    /// Consumes the `FramedHeaderRead`, returning its underlying I/O stream.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise being
+    /// worked with.
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: AsyncRead> Stream for FramedHeaderRead<T> {
+    type Item = (Header, BytesMut);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<(Header, BytesMut)>, io::Error> {
+        self.inner.poll()
+    }
+}
+
+// ===== impl FramedStreamRead =====
+
+impl<T> FramedStreamRead<T> {
+    /// Returns a reference to the codec used for decoding.
+    pub fn decoder(&self) -> &LengthDelimitedCodec {
+        &self.inner.decoder().codec
+    }
+
+    /// Returns a reference to the underlying I/O stream wrapped by
+    /// `FramedStreamRead`.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise
+    /// being worked with.
+    pub fn get_ref(&self) -> &T {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying I/O stream wrapped by
+    /// `FramedStreamRead`.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise being
+    /// worked with.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+
+    /// Consumes the `FramedStreamRead`, returning its underlying I/O stream.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise being
+    /// worked with.
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: AsyncRead> Stream for FramedStreamRead<T> {
+    type Item = FrameEvent;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<FrameEvent>, io::Error> {
+        self.inner.poll()
+    }
+}
+
+// ===== impl FramedHeaderWrite =====
+
+impl<T, B: IntoBuf> FramedHeaderWrite<T, B> {
+    /// Returns a reference to the underlying I/O stream wrapped by
+    /// `FramedHeaderWrite`.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise
+    /// being worked with.
+    pub fn get_ref(&self) -> &T {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying I/O stream wrapped by
+    /// `FramedHeaderWrite`.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise being
+    /// worked with.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+
+    /// Consumes the `FramedHeaderWrite`, returning its underlying I/O stream.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise being
+    /// worked with.
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: AsyncWrite, B: IntoBuf> Sink for FramedHeaderWrite<T, B> {
+    type SinkItem = (Header, B);
+    type SinkError = io::Error;
+
+    fn start_send(&mut self, item: (Header, B)) -> StartSend<(Header, B), io::Error> {
+        if !try!(self.inner.poll_room()).is_ready() {
+            return Ok(AsyncSink::NotReady(item));
+        }
+
+        let (header, buf) = item;
+
+        if header.prefix.len() != self.inner.builder.length_field_offset {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "header prefix length does not match length field offset"));
+        }
+
+        try!(self.inner.set_frame(&header.prefix, buf.into_buf()));
+
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), io::Error> {
+        self.inner.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), io::Error> {
+        self.inner.close()
+    }
+}
+
+impl<T, B: IntoBuf> fmt::Debug for FramedHeaderWrite<T, B>
+    where T: fmt::Debug,
+          B::Buf: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FramedHeaderWrite")
+            .field("inner", &self.inner)
             .finish()
     }
 }
 
+// ===== impl Header =====
+
+impl Header {
+    /// Creates a new `Header` with the given prefix, to be written by a
+    /// `FramedHeaderWrite`.
+    ///
+    /// The length of the header is zero. The length field of the encoded
+    /// frame is derived from the payload.
+    pub fn new(prefix: BytesMut) -> Header {
+        Header {
+            prefix: prefix,
+            length: 0,
+        }
+    }
+
+    /// Returns the bytes found before the length field.
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Returns the value of the length field, as read from the frame head.
+    ///
+    /// This is the value before `length_adjustment` is applied.
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Consumes the `Header`, returning the bytes found before the length
+    /// field.
+    pub fn into_prefix(self) -> BytesMut {
+        self.prefix
+    }
+}
+
 // ===== impl Builder =====
 
 impl Builder {
@@ -586,6 +1407,9 @@ impl Builder {
             // Default max frame length of 8MB
             max_frame_len: 8 * 1_024 * 1_024,
 
+            // Frames read piece by piece are not buffered, so no max length
+            max_stream_frame_len: usize::max_value(),
+
             // Default byte length of 4
             length_field_len: 4,
 
@@ -600,6 +1424,20 @@ impl Builder {
 
             // Default to reading the length field in network (big) endian.
             length_field_is_big_endian: true,
+
+            // Default to a fixed width length field.
+            length_field_is_varint: false,
+
+            // Default to frames without a checksum.
+            checksum: None,
+
+            // Default to failing on frames that are too big.
+            frame_too_big: FrameTooBigPolicy::Error,
+
+            // Default to writing frames one at a time.
+            max_queued_frames: 1,
+
+            max_queued_bytes: 64 * 1_024,
         }
     }
 
@@ -623,6 +1461,7 @@ impl Builder {
     /// ```
     pub fn big_endian(&mut self) -> &mut Self {
         self.length_field_is_big_endian = true;
+        self.length_field_is_varint = false;
         self
     }
 
@@ -646,6 +1485,173 @@ impl Builder {
     /// ```
     pub fn little_endian(&mut self) -> &mut Self {
         self.length_field_is_big_endian = false;
+        self.length_field_is_varint = false;
+        self
+    }
+
+    /// Read the length field as an unsigned LEB128 varint
+    ///
+    /// The length field then takes 1 to 10 bytes, 7 bits per byte starting
+    /// with the least significant ones, the high bit of each byte being set
+    /// when more bytes follow. This is the encoding used by protobuf for
+    /// delimited messages. The `length_field_length` setting is ignored, and
+    /// calling `big_endian` or `little_endian` switches back to fixed width
+    /// length fields.
+    ///
+    /// When decoding, overlong encodings, such as ones with trailing zero
+    /// bytes or values not fitting in a `u64`, are rejected with an
+    /// `InvalidData` error.
+    ///
+    /// This configuration option applies to both encoding and decoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_io::AsyncRead;
+    /// use tokio_io::codec::length_delimited::Builder;
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// Builder::new()
+    ///     .varint()
+    ///     .new_read(io);
+    /// # }
+    /// ```
+    pub fn varint(&mut self) -> &mut Self {
+        self.length_field_is_varint = true;
+        self
+    }
+
+    /// Append a checksum trailer to each frame
+    ///
+    /// The checksum of the frame contents, as given to the encoder or yielded
+    /// by the decoder, is written in 4 bytes right after the payload. The
+    /// trailer is not counted by the length field, and is written in the byte
+    /// order set by `big_endian` or `little_endian`.
+    ///
+    /// When decoding, the trailer is verified and stripped from the frame. A
+    /// mismatch is returned as an `io::Error` of kind `InvalidData`, with a
+    /// [`ChecksumMismatch`] payload telling which frame failed. The frame is
+    /// consumed, so reading can go on with the next frame.
+    ///
+    /// By default, frames have no checksum. This configuration option applies
+    /// to both encoding and decoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_io::AsyncRead;
+    /// use tokio_io::codec::length_delimited::{Builder, Checksum};
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// Builder::new()
+    ///     .checksum(Checksum::Crc32c)
+    ///     .new_read(io);
+    /// # }
+    /// ```
+    ///
+    /// [`ChecksumMismatch`]: struct.ChecksumMismatch.html
+    pub fn checksum(&mut self, val: Checksum) -> &mut Self {
+        self.checksum = Some(val);
+        self
+    }
+
+    /// Sets what to do when decoding a frame longer than the max frame length
+    ///
+    /// With `FrameTooBigPolicy::Error`, the default, a `FrameTooBig` error is
+    /// returned and no more frames can be read.
+    ///
+    /// With `FrameTooBigPolicy::Skip`, no error is returned. The bytes of the
+    /// frame are discarded as they come in, without being buffered, and
+    /// decoding resumes with the next frame. The frames skipped so far are
+    /// counted by `LengthDelimitedCodec::skipped_frames`, which the read halves
+    /// expose through `decoder`. A `FramedStreamRead` also yields a
+    /// `FrameEvent::Skipped` event for every frame skipped.
+    ///
+    /// This configuration option only applies to decoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_io::AsyncRead;
+    /// use tokio_io::codec::length_delimited::{Builder, FrameTooBigPolicy};
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// Builder::new()
+    ///     .max_frame_length(64 * 1024)
+    ///     .on_frame_too_big(FrameTooBigPolicy::Skip)
+    ///     .new_read(io);
+    /// # }
+    /// ```
+    pub fn on_frame_too_big(&mut self, val: FrameTooBigPolicy) -> &mut Self {
+        self.frame_too_big = val;
+        self
+    }
+
+    /// Sets the maximum number of frames queued for writing
+    ///
+    /// Frames sent to a `FramedWrite` are queued until they are written, so
+    /// that a burst of small frames can be written to the underlying I/O
+    /// object together, with vectored writes. Once `val` frames are queued,
+    /// or at least `max_queued_bytes`, no more frames are accepted until some
+    /// of them are written.
+    ///
+    /// The default value is 1, so that frames are written one at a time.
+    ///
+    /// This configuration option only applies to encoding.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `val` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// # use tokio_io::AsyncWrite;
+    /// # use tokio_io::codec::length_delimited;
+    /// # use bytes::BytesMut;
+    /// # fn write_frame<T: AsyncWrite>(io: T) {
+    /// # let _: length_delimited::FramedWrite<T, BytesMut> =
+    /// length_delimited::Builder::new()
+    ///     .max_queued_frames(32)
+    ///     .max_queued_bytes(16 * 1024)
+    ///     .new_write(io);
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    pub fn max_queued_frames(&mut self, val: usize) -> &mut Self {
+        assert!(val > 0, "at least one frame must be queued");
+        self.max_queued_frames = val;
+        self
+    }
+
+    /// Sets the number of queued bytes from which no more frames are queued
+    ///
+    /// See `max_queued_frames` for more detail. A frame is always accepted
+    /// when the queue is empty, whatever its size. The default value is 64KB.
+    ///
+    /// This configuration option only applies to encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// # use tokio_io::AsyncWrite;
+    /// # use tokio_io::codec::length_delimited;
+    /// # use bytes::BytesMut;
+    /// # fn write_frame<T: AsyncWrite>(io: T) {
+    /// # let _: length_delimited::FramedWrite<T, BytesMut> =
+    /// length_delimited::Builder::new()
+    ///     .max_queued_frames(32)
+    ///     .max_queued_bytes(16 * 1024)
+    ///     .new_write(io);
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    pub fn max_queued_bytes(&mut self, val: usize) -> &mut Self {
+        self.max_queued_bytes = val;
         self
     }
 
@@ -662,6 +1668,9 @@ impl Builder {
     /// When frames exceed the max length, an `io::Error` with the custom value
     /// of the `FrameTooBig` type will be returned.
     ///
+    /// This setting does not apply to `FramedStreamRead`, which has its own
+    /// limit, see `max_stream_frame_length`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -679,6 +1688,32 @@ impl Builder {
         self
     }
 
+    /// Sets the max frame length when reading frames piece by piece
+    ///
+    /// This configuration option only applies to `FramedStreamRead`. As frames
+    /// are not buffered whole, there is no limit by default.
+    ///
+    /// The length field is checked against this setting like with
+    /// `max_frame_length`, and frames over it are handled as configured by
+    /// `on_frame_too_big`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_io::AsyncRead;
+    /// use tokio_io::codec::length_delimited::Builder;
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// Builder::new()
+    ///     .max_stream_frame_length(1024 * 1024 * 1024)
+    ///     .new_stream_read(io);
+    /// # }
+    /// ```
+    pub fn max_stream_frame_length(&mut self, val: usize) -> &mut Self {
+        self.max_stream_frame_len = val;
+        self
+    }
+
     /// Sets the number of bytes used to represent the length field
     ///
     /// The default value is `4`. The max value is `8`.
@@ -788,10 +1823,7 @@ impl Builder {
         where T: AsyncRead,
     {
         FramedRead {
-            inner: codec::FramedRead::new(upstream, Decoder {
-                builder: *self,
-                state: DecodeState::Head,
-            }),
+            inner: codec::FramedRead::new(upstream, self.new_codec()),
         }
     }
 
@@ -820,7 +1852,10 @@ impl Builder {
         FramedWrite {
             inner: inner,
             builder: *self,
-            frame: None,
+            queue: FrameQueue {
+                frames: VecDeque::new(),
+                remaining: 0,
+            },
         }
     }
 
@@ -850,16 +1885,250 @@ impl Builder {
         Framed { inner: inner }
     }
 
-    fn num_head_bytes(&self) -> usize {
-        let num = self.length_field_offset + self.length_field_len;
-        cmp::max(num, self.num_skip.unwrap_or(0))
+    /// Create a configured length delimited `FramedHeaderRead`
+    ///
+    /// Frames are yielded along with a [`Header`] holding the bytes before the
+    /// length field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_io::AsyncRead;
+    /// use tokio_io::codec::length_delimited::Builder;
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// // A one byte tag followed by a `u16` length field
+    /// Builder::new()
+    ///     .length_field_offset(1)
+    ///     .length_field_length(2)
+    ///     .new_header_read(io);
+    /// # }
+    /// ```
+    ///
+    /// [`Header`]: struct.Header.html
+    pub fn new_header_read<T>(&self, upstream: T) -> FramedHeaderRead<T>
+        where T: AsyncRead,
+    {
+        FramedHeaderRead {
+            inner: codec::FramedRead::new(upstream, HeaderDecoder {
+                codec: self.new_codec(),
+            }),
+        }
     }
 
-    fn get_num_skip(&self) -> usize {
-        self.num_skip.unwrap_or(self.length_field_offset + self.length_field_len)
+    /// Create a configured length delimited `FramedHeaderWrite`
+    ///
+    /// Frames are submitted along with a [`Header`], whose prefix is written
+    /// before the length field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// # use tokio_io::AsyncWrite;
+    /// # use tokio_io::codec::length_delimited;
+    /// # use bytes::BytesMut;
+    /// # fn write_frame<T: AsyncWrite>(io: T) {
+    /// # let _: length_delimited::FramedHeaderWrite<T, BytesMut> =
+    /// length_delimited::Builder::new()
+    ///     .length_field_offset(1)
+    ///     .length_field_length(2)
+    ///     .new_header_write(io);
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    ///
+    /// [`Header`]: struct.Header.html
+    pub fn new_header_write<T, B>(&self, inner: T) -> FramedHeaderWrite<T, B>
+        where T: AsyncWrite,
+              B: IntoBuf,
+    {
+        FramedHeaderWrite {
+            inner: self.new_write(inner),
+        }
+    }
+
+    /// Create a configured length delimited `FramedStreamRead`
+    ///
+    /// Instead of whole frames, a [`FrameEvent::Start`] is yielded as soon as
+    /// a frame head is read, followed by the payload in chunks as it arrives
+    /// and a [`FrameEvent::End`]. The payload is never buffered whole, so
+    /// frames are not checked against `max_frame_length`, only against
+    /// `max_stream_frame_length`, which is unlimited by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_io::AsyncRead;
+    /// use tokio_io::codec::length_delimited::Builder;
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// Builder::new()
+    ///     .length_field_length(8)
+    ///     .new_stream_read(io);
+    /// # }
+    /// ```
+    ///
+    /// [`FrameEvent::Start`]: enum.FrameEvent.html#variant.Start
+    /// [`FrameEvent::End`]: enum.FrameEvent.html#variant.End
+    pub fn new_stream_read<T>(&self, upstream: T) -> FramedStreamRead<T>
+        where T: AsyncRead,
+    {
+        FramedStreamRead {
+            inner: codec::FramedRead::new(upstream, StreamDecoder {
+                codec: self.new_codec(),
+                crc: !0,
+            }),
+        }
+    }
+
+    /// Create a configured `LengthDelimitedCodec`
+    ///
+    /// The codec can be used with the generic `codec` types, such as
+    /// `codec::Framed` or `codec::FramedRead`, instead of the length delimited
+    /// framers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_io::{AsyncRead, AsyncWrite};
+    /// use tokio_io::codec::length_delimited::{Builder, LengthDelimitedCodec};
+    /// use tokio_io::codec::Framed;
+    ///
+    /// # fn bind_framed<T: AsyncRead + AsyncWrite>(io: T)
+    /// #     -> Framed<T, LengthDelimitedCodec> {
+    /// io.framed(Builder::new()
+    ///     .length_field_length(2)
+    ///     .new_codec())
+    /// # }
+    /// ```
+    pub fn new_codec(&self) -> LengthDelimitedCodec {
+        LengthDelimitedCodec {
+            builder: *self,
+            state: DecodeState::Head,
+            header: None,
+            frames: 0,
+            skipped: 0,
+            resync: Resync::Ready,
+        }
+    }
+
+    // Encodes the head of a frame with a payload of `n` bytes.
+    fn encode_head(&self, n: usize, dst: &mut BytesMut) -> io::Result<()> {
+        if n > self.max_frame_len {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, FrameTooBig {
+                _priv: (),
+            }));
+        }
+
+        // Adjust `n` with bounds checking
+        let n = if self.length_adjustment < 0 {
+            n.checked_add(-self.length_adjustment as usize)
+        } else {
+            n.checked_sub(self.length_adjustment as usize)
+        };
+
+        // Error handling
+        let n = match n {
+            Some(n) => n,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "provided length would overflow after adjustment")),
+        };
+
+        if self.length_field_is_varint {
+            let mut n = n as u64;
+            dst.reserve(MAX_VARINT_LEN);
+
+            while n >= 0x80 {
+                dst.put_u8(n as u8 | 0x80);
+                n >>= 7;
+            }
+            dst.put_u8(n as u8);
+
+            return Ok(());
+        }
+
+        dst.reserve(self.length_field_len);
+
+        if self.length_field_is_big_endian {
+            dst.put_uint::<BigEndian>(n as u64, self.length_field_len);
+        } else {
+            dst.put_uint::<LittleEndian>(n as u64, self.length_field_len);
+        }
+
+        Ok(())
+    }
+
+    // Decodes the length field at the start of `src`, returning its value
+    // and its length in bytes.
+    fn decode_length_field(&self, src: &[u8]) -> io::Result<Option<(u64, usize)>> {
+        if !self.length_field_is_varint {
+            let field_len = self.length_field_len;
+
+            if src.len() < field_len {
+                return Ok(None);
+            }
+
+            let mut src = Cursor::new(src);
+
+            // match endianess
+            let n = if self.length_field_is_big_endian {
+                src.get_uint::<BigEndian>(field_len)
+            } else {
+                src.get_uint::<LittleEndian>(field_len)
+            };
+
+            return Ok(Some((n, field_len)));
+        }
+
+        let mut n = 0u64;
+
+        for (i, &b) in src.iter().take(MAX_VARINT_LEN).enumerate() {
+            // The 10th byte only has room for the top bit of a `u64`
+            if i == MAX_VARINT_LEN - 1 && b > 1 {
+                return Err(invalid_varint());
+            }
+
+            n |= ((b & 0x7f) as u64) << (7 * i);
+
+            if b & 0x80 == 0 {
+                // A last byte of zero could have been left out
+                if b == 0 && i > 0 {
+                    return Err(invalid_varint());
+                }
+                return Ok(Some((n, i + 1)));
+            }
+        }
+
+        if src.len() >= MAX_VARINT_LEN {
+            return Err(invalid_varint());
+        }
+
+        Ok(None)
+    }
+
+    fn trailer_len(&self) -> usize {
+        if self.checksum.is_some() {
+            checksum::TRAILER_LEN
+        } else {
+            0
+        }
+    }
+
+    fn num_head_bytes(&self) -> usize {
+        let field_len = if self.length_field_is_varint {
+            1
+        } else {
+            self.length_field_len
+        };
+        let num = self.length_field_offset + field_len;
+        cmp::max(num, self.num_skip.unwrap_or(0))
     }
 }
 
+fn invalid_varint() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid varint length field")
+}
 
 // ===== impl FrameTooBig =====
 
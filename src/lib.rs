@@ -15,12 +15,14 @@ extern crate log;
 #[macro_use]
 extern crate futures;
 extern crate bytes;
+extern crate iovec;
 
 use std::io as std_io;
 use std::io::Write;
 
 use futures::{Async, Future, Poll, Stream};
 use bytes::{Buf, BufMut};
+use iovec::IoVec;
 
 /// A convenience typedef around a `Future` whose error component is `io::Error`
 pub type IoFuture<T> = Box<Future<Item = T, Error = std_io::Error> + Send>;
@@ -49,20 +51,34 @@ pub mod io;
 pub mod codec;
 
 mod allow_std;
+mod buf_reader;
+mod buf_writer;
+mod checksum;
+mod codec_adapters;
 mod codecs;
 mod copy;
+mod copy_bidirectional;
+mod copy_buf;
+mod duplex;
+mod encode_buf;
 mod flush;
 mod framed;
 mod framed_read;
+mod framed_split;
 mod framed_write;
+mod layered;
 mod length_delimited;
 mod lines;
+mod mock;
 mod read;
 mod read_exact;
 mod read_to_end;
 mod read_until;
 mod shutdown;
+mod sink_writer;
 mod split;
+mod split_ref;
+mod stream_reader;
 mod window;
 mod write_all;
 
@@ -155,6 +171,23 @@ pub trait AsyncRead: std_io::Read {
         }
     }
 
+    /// Pull some bytes from this source into the specified buffers, filling
+    /// them in order and returning how many bytes were read in total.
+    ///
+    /// This is the vectored ("scatter") form of `read`. The default
+    /// implementation only reads into the first buffer, so objects which can
+    /// fill several buffers with a single system call (e.g. through `readv`)
+    /// are encouraged to override it.
+    ///
+    /// Like `read`, this returns `Async::Ready(0)` once EOF has been reached,
+    /// and `Async::NotReady` if the object is not currently readable.
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, std_io::Error> {
+        match bufs.first_mut() {
+            Some(buf) => Ok(Async::Ready(try_nb!(self.read(buf)))),
+            None => Ok(Async::Ready(0)),
+        }
+    }
+
     /// Provides a `Stream` and `Sink` interface for reading and writing to this
     /// `Io` object, using `Decode` and `Encode` to read and write the raw data.
     ///
@@ -182,6 +215,10 @@ pub trait AsyncRead: std_io::Read {
     ///
     /// The two halves returned implement the `Read` and `Write` traits,
     /// respectively.
+    ///
+    /// The halves share a lock around the object. If the object can be read
+    /// and written through a shared reference, `io::split_ref` and
+    /// `io::split_arc` avoid that lock.
     fn split(self) -> (ReadHalf<Self>, WriteHalf<Self>)
         where Self: AsyncWrite + Sized,
     {
@@ -193,12 +230,20 @@ impl<T: ?Sized + AsyncRead> AsyncRead for Box<T> {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         (**self).prepare_uninitialized_buffer(buf)
     }
+
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, std_io::Error> {
+        (**self).read_bufs(bufs)
+    }
 }
 
 impl<'a, T: ?Sized + AsyncRead> AsyncRead for &'a mut T {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         (**self).prepare_uninitialized_buffer(buf)
     }
+
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, std_io::Error> {
+        (**self).read_bufs(bufs)
+    }
 }
 
 impl<'a> AsyncRead for &'a [u8] {
@@ -207,6 +252,77 @@ impl<'a> AsyncRead for &'a [u8] {
     }
 }
 
+/// A trait for buffered readable objects which operate in an asynchronous and
+/// futures-aware fashion.
+///
+/// This is the asynchronous analogue of [`io::BufRead`]. Rather than
+/// returning a "would block" error from `fill_buf`, implementations return
+/// `Async::NotReady` from `poll_fill_buf` and arrange for the current task to
+/// be notified once more data is available. Any data that was already
+/// buffered is never lost across a `NotReady`.
+///
+/// [`io::BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+pub trait AsyncBufRead: AsyncRead {
+    /// Attempts to return the contents of the internal buffer, filling it with
+    /// more data from the inner reader if it is empty.
+    ///
+    /// On success, returns `Ok(Async::Ready(buf))`. An empty buffer indicates
+    /// that the underlying reader has reached EOF.
+    ///
+    /// If no data is available, `Ok(Async::NotReady)` is returned and the
+    /// current task is scheduled to be notified once the object is readable
+    /// again.
+    ///
+    /// This function is a lower-level call. It needs to be paired with the
+    /// [`consume`] method to function properly. When calling this method,
+    /// none of the contents will be "read" in the sense that later calling
+    /// `read` may return the same contents. As such, [`consume`] must be
+    /// called with the number of bytes that are consumed from this buffer to
+    /// ensure that the bytes are never returned twice.
+    ///
+    /// [`consume`]: #tymethod.consume
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error>;
+
+    /// Tells this buffer that `amt` bytes have been consumed from the buffer,
+    /// so they should no longer be returned in calls to `poll_fill_buf`.
+    ///
+    /// The `amt` must be `<=` the number of bytes in the buffer returned by
+    /// [`poll_fill_buf`].
+    ///
+    /// [`poll_fill_buf`]: #tymethod.poll_fill_buf
+    fn consume(&mut self, amt: usize);
+}
+
+impl<T: ?Sized + AsyncBufRead> AsyncBufRead for Box<T> {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error> {
+        (**self).poll_fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+}
+
+impl<'a, T: ?Sized + AsyncBufRead> AsyncBufRead for &'a mut T {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error> {
+        (**self).poll_fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+}
+
+impl<'a> AsyncBufRead for &'a [u8] {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error> {
+        Ok(Async::Ready(*self))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        *self = &self[amt..];
+    }
+}
+
 /// A trait for writable objects which operated in an asynchronous and
 /// futures-aware fashion.
 ///
@@ -299,6 +415,12 @@ pub trait AsyncWrite: std_io::Write {
     ///
     /// Note that this method will advance the `buf` provided automatically by
     /// the number of bytes written.
+    ///
+    /// Buffers made of several chunks, such as a `Chain` of a frame header
+    /// and its payload, are handed to [`write_bufs`] in one go, so objects
+    /// supporting vectored writes only need a single call to write them.
+    ///
+    /// [`write_bufs`]: #method.write_bufs
     fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, std_io::Error>
         where Self: Sized,
     {
@@ -306,21 +428,56 @@ pub trait AsyncWrite: std_io::Write {
             return Ok(Async::Ready(0));
         }
 
-        let n = try_nb!(self.write(buf.bytes()));
+        let n = {
+            // `IoVec`s can't be empty, so a placeholder fills the slots
+            // until `bytes_vec` overwrites them with the real chunks.
+            static DUMMY: &'static [u8] = &[0];
+            let mut bufs = [<&IoVec>::from(DUMMY); MAX_BUFS];
+            let cnt = buf.bytes_vec(&mut bufs);
+            try_ready!(self.write_bufs(&bufs[..cnt]))
+        };
         buf.advance(n);
         Ok(Async::Ready(n))
     }
+
+    /// Write bytes from the specified buffers, in order, returning how many
+    /// bytes were written in total.
+    ///
+    /// This is the vectored ("gather") form of `write`. The default
+    /// implementation only writes the first buffer, so objects which can
+    /// write several buffers with a single system call (e.g. through
+    /// `writev`) are encouraged to override it.
+    ///
+    /// Like `write`, this returns `Async::NotReady` if the object is not
+    /// currently writable.
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, std_io::Error> {
+        match bufs.first() {
+            Some(buf) => Ok(Async::Ready(try_nb!(self.write(buf)))),
+            None => Ok(Async::Ready(0)),
+        }
+    }
 }
 
+// Maximum number of chunks gathered from a `Buf` by `AsyncWrite::write_buf`.
+const MAX_BUFS: usize = 64;
+
 impl<T: ?Sized + AsyncWrite> AsyncWrite for Box<T> {
     fn shutdown(&mut self) -> Poll<(), std_io::Error> {
         (**self).shutdown()
     }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, std_io::Error> {
+        (**self).write_bufs(bufs)
+    }
 }
 impl<'a, T: ?Sized + AsyncWrite> AsyncWrite for &'a mut T {
     fn shutdown(&mut self) -> Poll<(), std_io::Error> {
         (**self).shutdown()
     }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, std_io::Error> {
+        (**self).write_bufs(bufs)
+    }
 }
 
 impl AsyncRead for std_io::Repeat {
@@ -361,9 +518,29 @@ impl<T: AsyncRead> AsyncRead for std_io::BufReader<T> {
     }
 }
 
+impl<T: AsyncRead> AsyncBufRead for std_io::BufReader<T> {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error> {
+        Ok(Async::Ready(try_nb!(std_io::BufRead::fill_buf(self))))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        std_io::BufRead::consume(self, amt)
+    }
+}
+
 impl<T: AsRef<[u8]>> AsyncRead for std_io::Cursor<T> {
 }
 
+impl<T: AsRef<[u8]>> AsyncBufRead for std_io::Cursor<T> {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error> {
+        Ok(Async::Ready(try!(std_io::BufRead::fill_buf(self))))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        std_io::BufRead::consume(self, amt)
+    }
+}
+
 impl<'a> AsyncWrite for std_io::Cursor<&'a mut [u8]> {
     fn shutdown(&mut self) -> Poll<(), std_io::Error> {
         Ok(().into())
@@ -385,5 +562,6 @@ impl AsyncWrite for std_io::Cursor<Box<[u8]>> {
 fn _assert_objects() {
     fn _assert<T>() {}
     _assert::<Box<AsyncRead>>();
+    _assert::<Box<AsyncBufRead>>();
     _assert::<Box<AsyncWrite>>();
 }
@@ -1,16 +1,17 @@
-use std::io::{self, BufRead};
+use std::io;
 use std::mem;
 
 use futures::{Poll, Stream};
 
-use AsyncRead;
+use AsyncBufRead;
+use read_until::read_until_internal;
 
 /// Combinator created by the top-level `lines` method which is a stream over
 /// the lines of text on an I/O object.
 #[derive(Debug)]
 pub struct Lines<A> {
     io: A,
-    line: String,
+    buf: Vec<u8>,
 }
 
 /// Creates a new stream from the I/O object given representing the lines of
@@ -20,11 +21,11 @@ pub struct Lines<A> {
 /// lines that the object contains. The returned stream will reach its end once
 /// `a` reaches EOF.
 pub fn lines<A>(a: A) -> Lines<A>
-    where A: AsyncRead + BufRead,
+    where A: AsyncBufRead,
 {
     Lines {
         io: a,
-        line: String::new(),
+        buf: Vec::new(),
     }
 }
 
@@ -39,22 +40,30 @@ impl<A> Lines<A> {
 }
 
 impl<A> Stream for Lines<A>
-    where A: AsyncRead + BufRead,
+    where A: AsyncBufRead,
 {
     type Item = String;
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Option<String>, io::Error> {
-        let n = try_nb!(self.io.read_line(&mut self.line));
-        if n == 0 && self.line.len() == 0 {
+        try_ready!(read_until_internal(&mut self.io, b'\n', &mut self.buf));
+        if self.buf.len() == 0 {
             return Ok(None.into())
         }
-        if self.line.ends_with("\n") {
-            self.line.pop();
-            if self.line.ends_with("\r") {
-                self.line.pop();
+        if self.buf.ends_with(b"\n") {
+            self.buf.pop();
+            if self.buf.ends_with(b"\r") {
+                self.buf.pop();
             }
         }
-        Ok(Some(mem::replace(&mut self.line, String::new())).into())
+
+        // The whole line is decoded at once, so a multi-byte character split
+        // across two reads is never mistaken for invalid data.
+        let buf = mem::replace(&mut self.buf, Vec::new());
+        match String::from_utf8(buf) {
+            Ok(line) => Ok(Some(line).into()),
+            Err(_) => Err(io::Error::new(io::ErrorKind::InvalidData,
+                                         "stream did not contain valid UTF-8")),
+        }
     }
 }
@@ -0,0 +1,233 @@
+use std::{cmp, fmt, mem, thread};
+use std::ascii;
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+
+use futures::Poll;
+use futures::task;
+
+use {AsyncRead, AsyncWrite};
+
+/// Scripts the behavior of a [`Mock`] I/O object.
+///
+/// Each call adds one step to the script. The built `Mock` then plays the
+/// steps back in order as the code under test reads from and writes to it.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_io;
+/// # extern crate futures;
+/// # use futures::Future;
+/// # fn main() {
+/// use tokio_io::io::{read_exact, write_all};
+/// use tokio_io::io::mock::Builder;
+///
+/// let io = Builder::new()
+///     .write(b"ping")
+///     .read(b"pong")
+///     .build();
+///
+/// let (io, _) = write_all(io, b"ping").wait().unwrap();
+/// let (_, buf) = read_exact(io, [0; 4]).wait().unwrap();
+/// assert_eq!(&buf, b"pong");
+/// # }
+/// ```
+///
+/// [`Mock`]: struct.Mock.html
+#[derive(Debug, Default)]
+pub struct Builder {
+    actions: VecDeque<Action>,
+}
+
+/// A scripted I/O object, created by a [`Builder`].
+///
+/// * Reads are served from the scripted `read` steps. A scripted read is
+///   handed out in one call if the destination is large enough, and never
+///   merged with the next one. Once the script is empty, reads return EOF.
+/// * Writes are checked byte by byte against the scripted `write` steps. A
+///   single call never covers more than one scripted write. Writing bytes that
+///   differ from the script, or writing when the script expects something
+///   else, panics.
+/// * Reading while the script expects a write, or a write error, panics.
+/// * `wait` steps return a "would block" error to whichever operation reaches
+///   them, and notify the current task so it is polled again.
+///
+/// Dropping a `Mock` before its script has been played out completely panics,
+/// unless the thread is already panicking.
+///
+/// [`Builder`]: struct.Builder.html
+#[derive(Debug)]
+pub struct Mock {
+    actions: VecDeque<Action>,
+}
+
+#[derive(Debug)]
+enum Action {
+    Read(Vec<u8>),
+    Write(Vec<u8>),
+    Wait,
+    ReadError(io::Error),
+    WriteError(io::Error),
+}
+
+// ===== impl Builder =====
+
+impl Builder {
+    /// Creates a new `Builder` with an empty script.
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Expects the code under test to read `buf`.
+    pub fn read(&mut self, buf: &[u8]) -> &mut Self {
+        self.actions.push_back(Action::Read(buf.into()));
+        self
+    }
+
+    /// Expects the code under test to write exactly `buf`.
+    pub fn write(&mut self, buf: &[u8]) -> &mut Self {
+        self.actions.push_back(Action::Write(buf.into()));
+        self
+    }
+
+    /// Returns a "would block" error to the next read or write.
+    pub fn wait(&mut self) -> &mut Self {
+        self.actions.push_back(Action::Wait);
+        self
+    }
+
+    /// Fails the next read with `error`.
+    pub fn read_error(&mut self, error: io::Error) -> &mut Self {
+        self.actions.push_back(Action::ReadError(error));
+        self
+    }
+
+    /// Fails the next write with `error`.
+    pub fn write_error(&mut self, error: io::Error) -> &mut Self {
+        self.actions.push_back(Action::WriteError(error));
+        self
+    }
+
+    /// Builds a `Mock` playing back the script.
+    ///
+    /// The script is moved into the returned `Mock`, leaving this builder
+    /// empty.
+    pub fn build(&mut self) -> Mock {
+        Mock {
+            actions: mem::replace(&mut self.actions, VecDeque::new()),
+        }
+    }
+}
+
+// ===== impl Mock =====
+
+impl Read for Mock {
+    fn read(&mut self, dst: &mut [u8]) -> io::Result<usize> {
+        match self.actions.pop_front() {
+            Some(Action::Read(mut data)) => {
+                let n = cmp::min(dst.len(), data.len());
+                dst[..n].copy_from_slice(&data[..n]);
+                if n < data.len() {
+                    data.drain(..n);
+                    self.actions.push_front(Action::Read(data));
+                }
+                Ok(n)
+            }
+            Some(Action::Wait) => {
+                task::current().notify();
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            Some(Action::ReadError(e)) => Err(e),
+            Some(action) => {
+                panic!("mock: unexpected read; next scripted step is {}", action);
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+impl AsyncRead for Mock {
+}
+
+impl Write for Mock {
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        match self.actions.pop_front() {
+            Some(Action::Write(mut expect)) => {
+                let n = cmp::min(src.len(), expect.len());
+                if src[..n] != expect[..n] {
+                    let at = src.iter().zip(&expect).position(|(a, b)| a != b).unwrap();
+                    panic!("mock: unexpected write\n\
+                            expected: b\"{}\"\n  \
+                            actual: b\"{}\"\n\
+                            first difference at byte {}",
+                           escape(&expect), escape(src), at);
+                }
+                if n < expect.len() {
+                    expect.drain(..n);
+                    self.actions.push_front(Action::Write(expect));
+                }
+                Ok(n)
+            }
+            Some(Action::Wait) => {
+                task::current().notify();
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            Some(Action::WriteError(e)) => Err(e),
+            Some(action) => {
+                panic!("mock: unexpected write of b\"{}\"; next scripted step is {}",
+                       escape(src), action);
+            }
+            None => {
+                panic!("mock: unexpected write of b\"{}\"; the script is over",
+                       escape(src));
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Mock {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(().into())
+    }
+}
+
+impl Drop for Mock {
+    fn drop(&mut self) {
+        if thread::panicking() || self.actions.is_empty() {
+            return;
+        }
+
+        let left = self.actions.iter()
+            .map(|action| action.to_string())
+            .collect::<Vec<_>>();
+        panic!("mock: dropped with {} scripted steps left: {}",
+               left.len(), left.join(", "));
+    }
+}
+
+// ===== impl Action =====
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Action::Read(ref data) => write!(f, "read(b\"{}\")", escape(data)),
+            Action::Write(ref data) => write!(f, "write(b\"{}\")", escape(data)),
+            Action::Wait => write!(f, "wait"),
+            Action::ReadError(ref e) => write!(f, "read_error({})", e),
+            Action::WriteError(ref e) => write!(f, "write_error({})", e),
+        }
+    }
+}
+
+fn escape(data: &[u8]) -> String {
+    let mut s = String::new();
+    for &b in data {
+        s.extend(ascii::escape_default(b).map(|c| c as char));
+    }
+    s
+}
@@ -1,9 +1,9 @@
-use std::io::{self, BufRead};
+use std::io;
 use std::mem;
 
-use futures::{Poll, Future};
+use futures::{Async, Poll, Future};
 
-use AsyncRead;
+use AsyncBufRead;
 
 /// A future which can be used to easily read the contents of a stream into a
 /// vector until the delimiter is reached.
@@ -37,7 +37,7 @@ enum State<A> {
 ///
 /// [`BufRead::read_until`]: https://doc.rust-lang.org/std/io/trait.BufRead.html#method.read_until
 pub fn read_until<A>(a: A, byte: u8, buf: Vec<u8>) -> ReadUntil<A>
-    where A: AsyncRead + BufRead,
+    where A: AsyncBufRead,
 {
     ReadUntil {
         state: State::Reading {
@@ -48,8 +48,38 @@ pub fn read_until<A>(a: A, byte: u8, buf: Vec<u8>) -> ReadUntil<A>
     }
 }
 
+// Appends bytes from `a` to `buf` until `byte` (included) or EOF is reached.
+//
+// Everything read so far is kept in `buf` when `NotReady` is returned, so this
+// can simply be called again once `a` is readable.
+pub fn read_until_internal<A>(a: &mut A, byte: u8, buf: &mut Vec<u8>) -> Poll<(), io::Error>
+    where A: AsyncBufRead + ?Sized,
+{
+    loop {
+        let (done, used) = {
+            let available = try_ready!(a.poll_fill_buf());
+            match available.iter().position(|b| *b == byte) {
+                Some(i) => {
+                    buf.extend_from_slice(&available[..i + 1]);
+                    (true, i + 1)
+                }
+                None => {
+                    buf.extend_from_slice(available);
+                    (available.is_empty(), available.len())
+                }
+            }
+        };
+
+        a.consume(used);
+
+        if done {
+            return Ok(Async::Ready(()));
+        }
+    }
+}
+
 impl<A> Future for ReadUntil<A>
-    where A: AsyncRead + BufRead
+    where A: AsyncBufRead
 {
     type Item = (A, Vec<u8>);
     type Error = io::Error;
@@ -57,11 +87,11 @@ impl<A> Future for ReadUntil<A>
     fn poll(&mut self) -> Poll<(A, Vec<u8>), io::Error> {
         match self.state {
             State::Reading { ref mut a, byte, ref mut buf } => {
-                // If we get `Ok(n)`, then we know the stream hit EOF or the delimiter.
-                // and just return it, as we are finished.
-                // If we hit "would block" then all the read data so far
-                // is in our buffer, and otherwise we propagate errors.
-                try_nb!(a.read_until(byte, buf));
+                // If we get `Ready`, then we know the stream hit EOF or the
+                // delimiter, and we are finished. If we hit "would block" then
+                // all the read data so far is in our buffer, and otherwise we
+                // propagate errors.
+                try_ready!(read_until_internal(a, byte, buf));
             },
             State::Empty => panic!("poll ReadUntil after it's done"),
         }
@@ -0,0 +1,102 @@
+use std::io::{self, Write};
+
+use futures::{Async, AsyncSink, Poll, Sink};
+
+use AsyncWrite;
+
+/// Adapts a `Sink` of byte chunks into an `AsyncWrite`.
+///
+/// Every write sends the written bytes as one item into the sink, so writes
+/// are only accepted as fast as the sink takes items. Flushing the writer
+/// drives the sink with `poll_complete`, and shutting it down closes the
+/// sink.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_io;
+/// # extern crate bytes;
+/// # extern crate futures;
+/// # use futures::Future;
+/// # fn main() {
+/// use tokio_io::codec::length_delimited;
+/// use tokio_io::io::{flush, write_all, SinkWriter};
+/// use bytes::Bytes;
+/// use std::io::Cursor;
+///
+/// let frames = length_delimited::FramedWrite::<_, Bytes>::new(Cursor::new(Vec::new()));
+///
+/// let (writer, _) = write_all(SinkWriter::new(frames), b"hello").wait().unwrap();
+/// let writer = flush(writer).wait().unwrap();
+///
+/// let io = writer.into_inner().into_inner();
+/// assert_eq!(io.get_ref(), b"\x00\x00\x00\x05hello");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SinkWriter<S> {
+    inner: S,
+}
+
+impl<S> SinkWriter<S>
+    where S: Sink<SinkError = io::Error>,
+          for<'a> S::SinkItem: From<&'a [u8]>,
+{
+    /// Creates a new `SinkWriter` sending written bytes into `sink`.
+    pub fn new(sink: S) -> SinkWriter<S> {
+        SinkWriter { inner: sink }
+    }
+}
+
+impl<S> SinkWriter<S> {
+    /// Returns a reference to the underlying sink.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consumes this `SinkWriter`, returning the underlying sink.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+fn would_block() -> io::Error {
+    io::Error::new(io::ErrorKind::WouldBlock, "would block")
+}
+
+impl<S> Write for SinkWriter<S>
+    where S: Sink<SinkError = io::Error>,
+          for<'a> S::SinkItem: From<&'a [u8]>,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        match try!(self.inner.start_send(buf.into())) {
+            AsyncSink::Ready => Ok(buf.len()),
+            AsyncSink::NotReady(_) => Err(would_block()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match try!(self.inner.poll_complete()) {
+            Async::Ready(()) => Ok(()),
+            Async::NotReady => Err(would_block()),
+        }
+    }
+}
+
+impl<S> AsyncWrite for SinkWriter<S>
+    where S: Sink<SinkError = io::Error>,
+          for<'a> S::SinkItem: From<&'a [u8]>,
+{
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.close()
+    }
+}
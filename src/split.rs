@@ -1,49 +1,218 @@
+use std::{error, fmt};
 use std::io::{self, Read, Write};
+use std::sync::Arc;
 
 use futures::{Async, Poll};
 use futures::sync::BiLock;
+use iovec::IoVec;
 
 use {AsyncRead, AsyncWrite};
 
 /// The readable half of an object returned from `AsyncRead::split`.
+///
+/// Once a read hits EOF, the read direction is considered closed by the peer
+/// and all further reads return EOF without touching the object. Dropping a
+/// `ReadHalf` has no effect on the `WriteHalf`.
 #[derive(Debug)]
 pub struct ReadHalf<T> {
     handle: BiLock<T>,
+    // Shared by both halves of a split, to tell pairs apart.
+    id: Arc<()>,
+    eof: bool,
 }
 
 /// The writable half of an object returned from `AsyncRead::split`.
+///
+/// Calling `shutdown` flushes and shuts down the object, which half-closes the
+/// write direction only for objects such as sockets. Writes fail with a
+/// `BrokenPipe` error from then on, while the `ReadHalf` keeps working.
 #[derive(Debug)]
 pub struct WriteHalf<T> {
     handle: BiLock<T>,
+    id: Arc<()>,
+    closed: Option<ClosedBy>,
 }
 
+/// Which side closed one direction of a split object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClosedBy {
+    /// This end closed the direction, by shutting down its `WriteHalf`.
+    Local,
+    /// The other end closed the direction. A `ReadHalf` read EOF, or a write
+    /// on a `WriteHalf` failed with a `BrokenPipe` error.
+    Peer,
+}
+
+/// Error indicating a `ReadHalf<T>` and `WriteHalf<T>` were not two halves of
+/// the same object, and thus could not be reunited.
+///
+/// Both halves are handed back unchanged.
+pub struct ReuniteError<T>(pub ReadHalf<T>, pub WriteHalf<T>);
+
 pub fn split<T: AsyncRead + AsyncWrite>(t: T) -> (ReadHalf<T>, WriteHalf<T>) {
     let (a, b) = BiLock::new(t);
-    (ReadHalf { handle: a }, WriteHalf { handle: b })
+    let id = Arc::new(());
+    let read = ReadHalf {
+        handle: a,
+        id: id.clone(),
+        eof: false,
+    };
+    let write = WriteHalf {
+        handle: b,
+        id: id,
+        closed: None,
+    };
+    (read, write)
+}
+
+// ===== impl ReadHalf =====
+
+impl<T> ReadHalf<T> {
+    /// Returns which side closed the read direction, if it is closed.
+    ///
+    /// The read direction can only be closed by the peer, so this returns
+    /// either `None` or `Some(ClosedBy::Peer)`.
+    pub fn closed_by(&self) -> Option<ClosedBy> {
+        if self.eof {
+            Some(ClosedBy::Peer)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether this `ReadHalf` and `other` come from the same call to
+    /// `split`.
+    pub fn is_pair_of(&self, other: &WriteHalf<T>) -> bool {
+        Arc::ptr_eq(&self.id, &other.id)
+    }
+
+    /// Reunites this `ReadHalf` with the `WriteHalf` it was split from,
+    /// returning the original object.
+    ///
+    /// This is useful to change the protocol spoken over an object after
+    /// splitting it, e.g. to upgrade a plain text connection to TLS.
+    ///
+    /// If the two halves did not come from the same call to `split`, they are
+    /// handed back in a `ReuniteError`.
+    pub fn reunite(self, other: WriteHalf<T>) -> Result<T, ReuniteError<T>> {
+        if !self.is_pair_of(&other) {
+            return Err(ReuniteError(self, other));
+        }
+
+        // The halves are a pair, so this can only succeed.
+        Ok(self.handle.reunite(other.handle).ok().expect("halves of the same split"))
+    }
+}
+
+// ===== impl WriteHalf =====
+
+impl<T> WriteHalf<T> {
+    /// Checks whether this `WriteHalf` and `other` come from the same call to
+    /// `split`.
+    pub fn is_pair_of(&self, other: &ReadHalf<T>) -> bool {
+        other.is_pair_of(self)
+    }
+
+    /// Returns which side closed the write direction, if it is closed.
+    pub fn closed_by(&self) -> Option<ClosedBy> {
+        self.closed
+    }
+}
+
+// ===== impl ReuniteError =====
+
+impl<T> fmt::Debug for ReuniteError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReuniteError")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for ReuniteError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(error::Error::description(self))
+    }
+}
+
+impl<T> error::Error for ReuniteError<T> {
+    fn description(&self) -> &str {
+        "tried to reunite halves that are not from the same split"
+    }
 }
 
+// ===== impl Read/Write =====
+
 fn would_block() -> io::Error {
     io::Error::new(io::ErrorKind::WouldBlock, "would block")
 }
 
 impl<T: AsyncRead> Read for ReadHalf<T> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        match self.handle.poll_lock() {
-            Async::Ready(mut l) => l.read(buf),
-            Async::NotReady => Err(would_block()),
+        if self.eof {
+            return Ok(0);
+        }
+
+        let n = match self.handle.poll_lock() {
+            Async::Ready(mut l) => try!(l.read(buf)),
+            Async::NotReady => return Err(would_block()),
+        };
+
+        if n == 0 && !buf.is_empty() {
+            self.eof = true;
         }
+
+        Ok(n)
     }
 }
 
 impl<T: AsyncRead> AsyncRead for ReadHalf<T> {
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        if self.eof {
+            return Ok(Async::Ready(0));
+        }
+
+        let n = match self.handle.poll_lock() {
+            Async::Ready(mut l) => try_ready!(l.read_bufs(bufs)),
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+
+        if n == 0 && bufs.iter().any(|buf| !buf.is_empty()) {
+            self.eof = true;
+        }
+
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<T> WriteHalf<T> {
+    fn check_open(&self) -> io::Result<()> {
+        match self.closed {
+            Some(_) => Err(io::Error::new(io::ErrorKind::BrokenPipe,
+                                          "write half is closed")),
+            None => Ok(()),
+        }
+    }
+
+    // Remembers that the peer closed the connection when a write tells so.
+    fn note_peer_close<U>(&mut self, res: io::Result<U>) -> io::Result<U> {
+        if let Err(ref e) = res {
+            if e.kind() == io::ErrorKind::BrokenPipe {
+                self.closed = Some(ClosedBy::Peer);
+            }
+        }
+        res
+    }
 }
 
 impl<T: AsyncWrite> Write for WriteHalf<T> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        match self.handle.poll_lock() {
+        try!(self.check_open());
+
+        let res = match self.handle.poll_lock() {
             Async::Ready(mut l) => l.write(buf),
-            Async::NotReady => Err(would_block()),
-        }
+            Async::NotReady => return Err(would_block()),
+        };
+        self.note_peer_close(res)
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -56,9 +225,29 @@ impl<T: AsyncWrite> Write for WriteHalf<T> {
 
 impl<T: AsyncWrite> AsyncWrite for WriteHalf<T> {
     fn shutdown(&mut self) -> Poll<(), io::Error> {
+        // Writes are refused as soon as shutting down starts, while the
+        // shutdown itself is retried until it completes.
+        if self.closed == Some(ClosedBy::Peer) {
+            return Ok(Async::Ready(()));
+        }
+        self.closed = Some(ClosedBy::Local);
+
         match self.handle.poll_lock() {
-            Async::Ready(mut l) => l.shutdown(),
-            Async::NotReady => Err(would_block()),
+            Async::Ready(mut l) => {
+                try_nb!(l.flush());
+                l.shutdown()
+            }
+            Async::NotReady => Ok(Async::NotReady),
         }
     }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        try!(self.check_open());
+
+        let res = match self.handle.poll_lock() {
+            Async::Ready(mut l) => l.write_bufs(bufs),
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+        self.note_peer_close(res)
+    }
 }
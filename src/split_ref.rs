@@ -0,0 +1,120 @@
+use std::io::{self, Read, Write};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use futures::Poll;
+use iovec::IoVec;
+
+use {AsyncRead, AsyncWrite};
+
+/// The readable half of an object split by [`split_ref`] or [`split_arc`].
+///
+/// [`split_ref`]: fn.split_ref.html
+/// [`split_arc`]: fn.split_arc.html
+#[derive(Debug)]
+pub struct RefReadHalf<P> {
+    inner: P,
+}
+
+/// The writable half of an object split by [`split_ref`] or [`split_arc`].
+///
+/// [`split_ref`]: fn.split_ref.html
+/// [`split_arc`]: fn.split_arc.html
+#[derive(Debug)]
+pub struct RefWriteHalf<P> {
+    inner: P,
+}
+
+/// Splits an object that is readable and writable through a shared reference
+/// into two borrowed halves.
+///
+/// Unlike [`AsyncRead::split`], the halves don't share a lock: every read and
+/// write goes straight to the object. This requires `&T` to implement
+/// `AsyncRead` and `AsyncWrite`, as is the case for many transports which
+/// support concurrent reads and writes, such as sockets.
+///
+/// [`AsyncRead::split`]: ../trait.AsyncRead.html#method.split
+pub fn split_ref<T: ?Sized>(t: &T) -> (RefReadHalf<&T>, RefWriteHalf<&T>)
+    where for<'a> &'a T: AsyncRead + AsyncWrite,
+{
+    (RefReadHalf { inner: t }, RefWriteHalf { inner: t })
+}
+
+/// Splits an object that is readable and writable through a shared reference
+/// into two owned halves.
+///
+/// This works like [`split_ref`], but the halves share ownership of the object
+/// through an `Arc`, so they can be moved into separate tasks.
+///
+/// [`split_ref`]: fn.split_ref.html
+pub fn split_arc<T: ?Sized>(t: Arc<T>) -> (RefReadHalf<Arc<T>>, RefWriteHalf<Arc<T>>)
+    where for<'a> &'a T: AsyncRead + AsyncWrite,
+{
+    (RefReadHalf { inner: t.clone() }, RefWriteHalf { inner: t })
+}
+
+// ===== impl RefReadHalf =====
+
+impl<P: Deref> RefReadHalf<P> {
+    /// Returns a reference to the underlying object.
+    pub fn get_ref(&self) -> &P::Target {
+        &self.inner
+    }
+}
+
+impl<P> Read for RefReadHalf<P>
+    where P: Deref,
+          for<'a> &'a P::Target: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self.inner).read(buf)
+    }
+}
+
+impl<P> AsyncRead for RefReadHalf<P>
+    where P: Deref,
+          for<'a> &'a P::Target: AsyncRead,
+{
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        (&*self.inner).prepare_uninitialized_buffer(buf)
+    }
+
+    fn read_bufs(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        (&*self.inner).read_bufs(bufs)
+    }
+}
+
+// ===== impl RefWriteHalf =====
+
+impl<P: Deref> RefWriteHalf<P> {
+    /// Returns a reference to the underlying object.
+    pub fn get_ref(&self) -> &P::Target {
+        &self.inner
+    }
+}
+
+impl<P> Write for RefWriteHalf<P>
+    where P: Deref,
+          for<'a> &'a P::Target: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self.inner).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self.inner).flush()
+    }
+}
+
+impl<P> AsyncWrite for RefWriteHalf<P>
+    where P: Deref,
+          for<'a> &'a P::Target: AsyncWrite,
+{
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        (&*self.inner).shutdown()
+    }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        (&*self.inner).write_bufs(bufs)
+    }
+}
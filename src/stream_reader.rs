@@ -0,0 +1,174 @@
+use std::{cmp, fmt};
+use std::io::{self, Read};
+
+use bytes::{Buf, IntoBuf};
+use futures::{Async, Poll, Stream};
+
+use {AsyncBufRead, AsyncRead};
+
+/// Adapts a `Stream` of byte chunks into an `AsyncRead`.
+///
+/// Reads are served from the chunks yielded by the stream, in order. A chunk
+/// that doesn't fit in the destination buffer is kept, and the rest of it is
+/// handed out by the next reads. The end of the stream is reported as EOF, and
+/// errors yielded by the stream are returned by the read that hits them.
+///
+/// `StreamReader` also implements [`AsyncBufRead`], giving direct access to
+/// the current chunk.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_io;
+/// # extern crate bytes;
+/// # extern crate futures;
+/// # use futures::Future;
+/// # fn main() {
+/// use tokio_io::io::{read_to_end, StreamReader};
+/// use bytes::Bytes;
+/// use futures::stream;
+///
+/// let chunks = stream::iter_ok(vec![Bytes::from("hello "), Bytes::from("world")]);
+///
+/// let (_, buf) = read_to_end(StreamReader::new(chunks), Vec::new()).wait().unwrap();
+/// assert_eq!(buf, b"hello world");
+/// # }
+/// ```
+///
+/// [`AsyncBufRead`]: ../trait.AsyncBufRead.html
+pub struct StreamReader<S>
+    where S: Stream,
+          S::Item: IntoBuf,
+{
+    inner: S,
+    chunk: Option<<S::Item as IntoBuf>::Buf>,
+    // Whether the stream yielded its last chunk, and must not be polled again
+    done: bool,
+}
+
+impl<S> StreamReader<S>
+    where S: Stream<Error = io::Error>,
+          S::Item: IntoBuf,
+{
+    /// Creates a new `StreamReader` reading from the chunks of `stream`.
+    pub fn new(stream: S) -> StreamReader<S> {
+        StreamReader {
+            inner: stream,
+            chunk: None,
+            done: false,
+        }
+    }
+
+    // Makes sure there's a chunk with some data left in it, unless the stream
+    // is done.
+    fn poll_chunk(&mut self) -> Poll<bool, io::Error> {
+        loop {
+            if let Some(ref chunk) = self.chunk {
+                if chunk.has_remaining() {
+                    return Ok(Async::Ready(true));
+                }
+            }
+
+            if self.done {
+                return Ok(Async::Ready(false));
+            }
+
+            match try_ready!(self.inner.poll()) {
+                Some(chunk) => self.chunk = Some(chunk.into_buf()),
+                None => {
+                    self.chunk = None;
+                    self.done = true;
+                    return Ok(Async::Ready(false));
+                }
+            }
+        }
+    }
+}
+
+impl<S> StreamReader<S>
+    where S: Stream,
+          S::Item: IntoBuf,
+{
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// Care should be taken to avoid polling the stream directly, which would
+    /// skip chunks.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consumes this `StreamReader`, returning the underlying stream.
+    ///
+    /// Note that what is left of a partly read chunk is lost.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> Read for StreamReader<S>
+    where S: Stream<Error = io::Error>,
+          S::Item: IntoBuf,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        match self.poll_chunk() {
+            Ok(Async::Ready(true)) => {}
+            Ok(Async::Ready(false)) => return Ok(0),
+            Ok(Async::NotReady) => {
+                return Err(io::Error::new(io::ErrorKind::WouldBlock, "would block"))
+            }
+            Err(e) => return Err(e),
+        }
+
+        let chunk = self.chunk.as_mut().unwrap();
+        let n = cmp::min(buf.len(), chunk.remaining());
+        chunk.copy_to_slice(&mut buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<S> AsyncRead for StreamReader<S>
+    where S: Stream<Error = io::Error>,
+          S::Item: IntoBuf,
+{
+}
+
+impl<S> AsyncBufRead for StreamReader<S>
+    where S: Stream<Error = io::Error>,
+          S::Item: IntoBuf,
+{
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], io::Error> {
+        if try_ready!(self.poll_chunk()) {
+            Ok(Async::Ready(self.chunk.as_ref().unwrap().bytes()))
+        } else {
+            Ok(Async::Ready(&[]))
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Some(ref mut chunk) = self.chunk {
+            let amt = cmp::min(amt, chunk.remaining());
+            chunk.advance(amt);
+        }
+    }
+}
+
+impl<S> fmt::Debug for StreamReader<S>
+    where S: Stream + fmt::Debug,
+          S::Item: IntoBuf,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StreamReader")
+            .field("stream", &self.inner)
+            .field("chunk_remaining", &self.chunk.as_ref().map(|c| c.remaining()))
+            .finish()
+    }
+}
@@ -0,0 +1,104 @@
+extern crate tokio_io;
+extern crate bytes;
+extern crate futures;
+
+use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_io::io::IoVec;
+use tokio_io::codec::length_delimited;
+use bytes::{Buf, Bytes, IntoBuf};
+use futures::{Async, Poll, Sink};
+
+use std::io::{self, Read, Write};
+
+// Records every write call, with one entry per chunk handed over at once.
+struct Vectored {
+    calls: Vec<Vec<Vec<u8>>>,
+}
+
+impl Write for Vectored {
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        self.calls.push(vec![src.to_vec()]);
+        Ok(src.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Vectored {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(().into())
+    }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        self.calls.push(bufs.iter().map(|b| b.to_vec()).collect());
+        Ok(Async::Ready(bufs.iter().fold(0, |n, b| n + b.len())))
+    }
+}
+
+#[test]
+fn write_buf_gathers_chunks() {
+    let mut w = Vectored { calls: vec![] };
+    let mut buf = Buf::chain(Bytes::from(&b"hello "[..]).into_buf(),
+                             Bytes::from(&b"world"[..]));
+
+    assert_eq!(w.write_buf(&mut buf).unwrap(), Async::Ready(11));
+    assert!(!buf.has_remaining());
+    assert_eq!(w.calls, vec![vec![b"hello ".to_vec(), b"world".to_vec()]]);
+}
+
+#[test]
+fn write_bufs_default_writes_first_chunk() {
+    let mut w = io::Cursor::new(Vec::new());
+    let bufs: [&IoVec; 2] = [b"hello "[..].into(), b"world"[..].into()];
+
+    assert_eq!(w.write_bufs(&bufs).unwrap(), Async::Ready(6));
+    assert_eq!(w.get_ref(), b"hello ");
+}
+
+#[test]
+fn write_buf_default_uses_write() {
+    let mut w = io::Cursor::new(Vec::new());
+    let mut buf = Buf::chain(Bytes::from(&b"hello "[..]).into_buf(),
+                             Bytes::from(&b"world"[..]));
+
+    assert_eq!(w.write_buf(&mut buf).unwrap(), Async::Ready(6));
+    assert_eq!(w.write_buf(&mut buf).unwrap(), Async::Ready(5));
+    assert_eq!(w.get_ref(), b"hello world");
+}
+
+#[test]
+fn read_bufs_default_fills_first_buffer() {
+    struct R;
+
+    impl Read for R {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            buf[..3].copy_from_slice(b"abc");
+            Ok(3)
+        }
+    }
+
+    impl AsyncRead for R {}
+
+    let mut a = [0; 4];
+    let mut b = [0; 4];
+    {
+        let mut bufs: [&mut IoVec; 2] = [(&mut a[..]).into(), (&mut b[..]).into()];
+        assert_eq!(R.read_bufs(&mut bufs).unwrap(), Async::Ready(3));
+    }
+    assert_eq!(&a, b"abc\0");
+    assert_eq!(&b, b"\0\0\0\0");
+}
+
+#[test]
+fn length_delimited_writes_head_and_payload_together() {
+    let mut io = length_delimited::Builder::new()
+        .length_field_length(2)
+        .new_write(Vectored { calls: vec![] });
+
+    assert!(io.start_send(Bytes::from(&b"abc"[..])).unwrap().is_ready());
+    assert!(io.poll_complete().unwrap().is_ready());
+
+    assert_eq!(io.get_ref().calls, vec![vec![b"\x00\x03".to_vec(), b"abc".to_vec()]]);
+}
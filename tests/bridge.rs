@@ -0,0 +1,123 @@
+extern crate tokio_io;
+extern crate bytes;
+extern crate futures;
+
+use tokio_io::{AsyncBufRead, AsyncWrite};
+use tokio_io::io::{copy, SinkWriter, StreamReader};
+use bytes::Bytes;
+use futures::{stream, Async, AsyncSink, Future, Poll, Sink, StartSend};
+
+use std::io::{self, Cursor, Read, Write};
+
+#[test]
+fn stream_reader_keeps_partial_chunks() {
+    let chunks = stream::iter_ok(vec![Bytes::from("hello"), Bytes::from(""), Bytes::from("world")]);
+    let mut rd = StreamReader::new(chunks);
+
+    let mut buf = [0; 3];
+    assert_eq!(rd.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"hel");
+    assert_eq!(rd.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"lo");
+    assert_eq!(rd.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"wor");
+
+    assert_eq!(rd.poll_fill_buf().unwrap(), Async::Ready(&b"ld"[..]));
+    rd.consume(2);
+    assert_eq!(rd.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn stream_reader_not_ready_and_errors() {
+    let mut calls = vec![
+        Err(io::Error::new(io::ErrorKind::Other, "boom")),
+        Ok(Async::NotReady),
+        Ok(Async::Ready(Some(Bytes::from("hi")))),
+    ];
+    let chunks = stream::poll_fn(move || calls.pop().unwrap());
+    let mut rd = StreamReader::new(chunks);
+
+    let mut buf = [0; 4];
+    assert_eq!(rd.read(&mut buf).unwrap(), 2);
+    assert_eq!(rd.read(&mut buf).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+    assert_eq!(rd.read(&mut buf).unwrap_err().kind(), io::ErrorKind::Other);
+}
+
+#[test]
+fn stream_reader_does_not_poll_after_end() {
+    let mut calls = vec![
+        Ok(Async::Ready(None)),
+        Ok(Async::Ready(Some(Bytes::from("hi")))),
+    ];
+    let chunks = stream::poll_fn(move || {
+        calls.pop().expect("stream polled after completion")
+    });
+    let mut rd = StreamReader::new(chunks);
+
+    let mut buf = [0; 4];
+    assert_eq!(rd.read(&mut buf).unwrap(), 2);
+    assert_eq!(rd.read(&mut buf).unwrap(), 0);
+    assert_eq!(rd.read(&mut buf).unwrap(), 0);
+    assert_eq!(rd.poll_fill_buf().unwrap(), Async::Ready(&b""[..]));
+}
+
+#[test]
+fn stream_reader_into_copy() {
+    let chunks = stream::iter_ok(vec![Bytes::from("hello "), Bytes::from("world")]);
+
+    let (amt, _, wr) = copy(StreamReader::new(chunks), Cursor::new(Vec::new())).wait().unwrap();
+    assert_eq!(amt, 11);
+    assert_eq!(wr.get_ref(), b"hello world");
+}
+
+#[test]
+fn sink_writer_maps_sink_calls() {
+    let mut wr = SinkWriter::new(MockSink {
+        items: vec![],
+        ready: false,
+        completed: 0,
+        closed: false,
+    });
+
+    assert_eq!(wr.write(b"hello").unwrap_err().kind(), io::ErrorKind::WouldBlock);
+    wr.get_mut().ready = true;
+    assert_eq!(wr.write(b"hello").unwrap(), 5);
+    assert_eq!(wr.write(b"").unwrap(), 0);
+
+    wr.flush().unwrap();
+    assert_eq!(wr.get_ref().completed, 1);
+
+    assert_eq!(wr.shutdown().unwrap(), Async::Ready(()));
+    assert!(wr.get_ref().closed);
+    assert_eq!(wr.into_inner().items, vec![Bytes::from("hello")]);
+}
+
+struct MockSink {
+    items: Vec<Bytes>,
+    ready: bool,
+    completed: usize,
+    closed: bool,
+}
+
+impl Sink for MockSink {
+    type SinkItem = Bytes;
+    type SinkError = io::Error;
+
+    fn start_send(&mut self, item: Bytes) -> StartSend<Bytes, io::Error> {
+        if !self.ready {
+            return Ok(AsyncSink::NotReady(item));
+        }
+        self.items.push(item);
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), io::Error> {
+        self.completed += 1;
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), io::Error> {
+        self.closed = true;
+        Ok(Async::Ready(()))
+    }
+}
@@ -0,0 +1,193 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::{AsyncBufRead, AsyncRead, AsyncWrite};
+use tokio_io::io::{self, BufReader, BufWriter};
+
+use futures::{Future, Poll, Stream};
+use futures::Async::{Ready, NotReady};
+
+use std::io::{Cursor, Read, Write};
+use std::collections::VecDeque;
+
+macro_rules! mock {
+    ($($x:expr,)*) => {{
+        let mut v = VecDeque::new();
+        v.extend(vec![$($x),*]);
+        Mock { calls: v, written: vec![] }
+    }};
+}
+
+#[test]
+fn buf_reader_keeps_data_across_would_block() {
+    let mut reader = BufReader::new(mock! {
+        Ok(b"hello ".to_vec()),
+        Err(would_block()),
+        Ok(b"world".to_vec()),
+    });
+
+    assert_eq!(reader.poll_fill_buf().unwrap(), Ready(&b"hello "[..]));
+    reader.consume(2);
+    assert_eq!(reader.buffer(), b"llo ");
+    assert_eq!(reader.poll_fill_buf().unwrap(), Ready(&b"llo "[..]));
+    reader.consume(4);
+
+    assert_eq!(reader.poll_fill_buf().unwrap(), NotReady);
+    assert_eq!(reader.poll_fill_buf().unwrap(), Ready(&b"world"[..]));
+    reader.consume(5);
+    assert_eq!(reader.poll_fill_buf().unwrap(), Ready(&b""[..]));
+}
+
+#[test]
+fn buf_reader_read_bypasses_buffer_for_large_reads() {
+    let mut reader = BufReader::with_capacity(2, mock! {
+        Ok(b"abcd".to_vec()),
+    });
+
+    let mut buf = [0; 4];
+    assert_eq!(reader.read(&mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"abcd");
+    assert_eq!(reader.buffer(), b"");
+}
+
+#[test]
+fn lines_split_utf8_across_would_block() {
+    let lines = io::lines(BufReader::new(mock! {
+        Ok(b"caf\xc3".to_vec()),
+        Err(would_block()),
+        Ok(b"\xa9\r\nsecond".to_vec()),
+    }));
+    let mut lines = lines;
+
+    assert_eq!(lines.poll().unwrap(), NotReady);
+    assert_eq!(lines.poll().unwrap(), Ready(Some("café".to_string())));
+    assert_eq!(lines.poll().unwrap(), Ready(Some("second".to_string())));
+    assert_eq!(lines.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn lines_invalid_utf8() {
+    let mut lines = io::lines(&b"\xff\n"[..]);
+    let err = lines.poll().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_until_across_would_block() {
+    let mut fut = io::read_until(BufReader::new(mock! {
+        Ok(b"ab".to_vec()),
+        Err(would_block()),
+        Ok(b"c;de".to_vec()),
+    }), b';', vec![]);
+
+    assert!(fut.poll().unwrap().is_not_ready());
+
+    let (mut reader, buf) = match fut.poll().unwrap() {
+        Ready(v) => v,
+        NotReady => panic!("not ready"),
+    };
+    assert_eq!(buf, b"abc;");
+    assert_eq!(reader.poll_fill_buf().unwrap(), Ready(&b"de"[..]));
+}
+
+#[test]
+fn buf_writer_coalesces_writes() {
+    let mut writer = BufWriter::with_capacity(8, mock!());
+
+    assert_eq!(writer.write(b"abc").unwrap(), 3);
+    assert_eq!(writer.write(b"def").unwrap(), 3);
+    assert!(writer.get_ref().written.is_empty());
+
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().written, vec![b"abcdef".to_vec()]);
+}
+
+#[test]
+fn buf_writer_keeps_unwritten_data_on_would_block() {
+    let mut writer = BufWriter::with_capacity(8, mock! {
+        Ok(b"ab".to_vec()),
+        Err(would_block()),
+        Ok(b"cd".to_vec()),
+    });
+
+    assert_eq!(writer.write(b"abcd").unwrap(), 4);
+    let err = writer.flush().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+    assert_eq!(writer.buffer(), b"cd");
+
+    assert_eq!(writer.shutdown().unwrap(), Ready(()));
+    assert_eq!(writer.buffer(), b"");
+}
+
+#[test]
+fn copy_buf_writes_from_reader_buffer() {
+    let reader = BufReader::with_capacity(4, &b"hello world"[..]);
+    let (amt, _, writer) = io::copy_buf(reader, Cursor::new(Vec::new())).wait().unwrap();
+
+    assert_eq!(amt, 11);
+    assert_eq!(writer.get_ref(), b"hello world");
+}
+
+#[test]
+fn copy_returns_unbuffered_reader() {
+    let (amt, reader, writer) = io::copy(&b"hello world"[..], Cursor::new(Vec::new())).wait().unwrap();
+
+    assert_eq!(amt, 11);
+    assert_eq!(reader, b"");
+    assert_eq!(writer.get_ref(), b"hello world");
+}
+
+// ===== Mock ======
+
+struct Mock {
+    calls: VecDeque<std::io::Result<Vec<u8>>>,
+    written: Vec<Vec<u8>>,
+}
+
+fn would_block() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::WouldBlock, "would block")
+}
+
+impl Read for Mock {
+    fn read(&mut self, dst: &mut [u8]) -> std::io::Result<usize> {
+        match self.calls.pop_front() {
+            Some(Ok(data)) => {
+                debug_assert!(dst.len() >= data.len());
+                dst[..data.len()].copy_from_slice(&data[..]);
+                Ok(data.len())
+            }
+            Some(Err(e)) => Err(e),
+            None => Ok(0),
+        }
+    }
+}
+
+impl AsyncRead for Mock {
+}
+
+impl Write for Mock {
+    fn write(&mut self, src: &[u8]) -> std::io::Result<usize> {
+        match self.calls.pop_front() {
+            Some(Ok(data)) => {
+                assert_eq!(&data[..], &src[..data.len()]);
+                self.written.push(data.clone());
+                Ok(data.len())
+            }
+            Some(Err(e)) => Err(e),
+            None => {
+                self.written.push(src.to_vec());
+                Ok(src.len())
+            }
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Mock {
+    fn shutdown(&mut self) -> Poll<(), std::io::Error> {
+        Ok(().into())
+    }
+}
@@ -1,8 +1,13 @@
 extern crate tokio_io;
 extern crate bytes;
+extern crate futures;
 
 use bytes::{BytesMut, Bytes, BufMut};
-use tokio_io::codec::{BytesCodec, LinesCodec, Decoder, Encoder};
+use futures::{Future, Sink, Stream};
+use tokio_io::AsyncRead;
+use tokio_io::codec::{BytesCodec, LinesCodec, Decoder, Encoder, FramedRead};
+
+use std::io::{self, Cursor};
 
 #[test]
 fn bytes_decoder() {
@@ -74,3 +79,102 @@ fn lines_encoder() {
     let mut buf = BytesMut::with_capacity(INITIAL_CAPACITY);
     codec.encode(Bytes::from_static(&[b'a'; INITIAL_CAPACITY + 1]), &mut buf).unwrap();
 }
+
+#[test]
+fn decoder_map() {
+    let mut codec = LinesCodec::new().map(|line| line.len());
+    let buf = &mut BytesMut::from(&b"one\nthree\nfo"[..]);
+    assert_eq!(Some(3), codec.decode(buf).unwrap());
+    assert_eq!(Some(5), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(Some(2), codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn decoder_and_then() {
+    let codec = LinesCodec::new().and_then(|line| {
+        line.parse::<u32>().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, line))
+    });
+    let io: &[u8] = b"1\n2\nthree\n4\n";
+
+    let mut stream = FramedRead::new(io, codec).wait();
+    assert_eq!(1, stream.next().unwrap().unwrap());
+    assert_eq!(2, stream.next().unwrap().unwrap());
+    let err = stream.next().unwrap().unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    assert_eq!("three", err.to_string());
+}
+
+#[derive(Debug)]
+struct MyError(io::Error);
+
+impl From<io::Error> for MyError {
+    fn from(e: io::Error) -> MyError {
+        MyError(e)
+    }
+}
+
+#[test]
+fn decoder_map_err() {
+    let mut codec = LinesCodec::new().map_err(|e| MyError(e));
+    let buf = &mut BytesMut::from(&b"\xff\n"[..]);
+    let MyError(e) = codec.decode(buf).unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, e.kind());
+}
+
+#[test]
+fn decoder_recover() {
+    let codec = LinesCodec::new()
+        .and_then(|line| {
+            line.parse::<u32>().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, line))
+        })
+        .recover();
+    let io: &[u8] = b"1\n\xff\ntwo\n4";
+
+    let mut stream = FramedRead::new(io, codec).wait();
+    assert_eq!(1, stream.next().unwrap().unwrap().unwrap());
+    let err = stream.next().unwrap().unwrap().unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    let err = stream.next().unwrap().unwrap().unwrap_err();
+    assert_eq!("two", err.to_string());
+    assert_eq!(4, stream.next().unwrap().unwrap().unwrap());
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn decoder_recover_unsupported() {
+    let mut codec = BytesCodec::new()
+        .and_then(|_| Err::<(), _>(io::Error::new(io::ErrorKind::InvalidData, "bad")))
+        .recover();
+    let buf = &mut BytesMut::from(&b"abc"[..]);
+
+    let err = codec.decode(buf).unwrap().unwrap().unwrap_err();
+    assert_eq!("bad", err.to_string());
+    let err = codec.decode(buf).unwrap_err();
+    assert_eq!(io::ErrorKind::Other, err.kind());
+}
+
+#[test]
+fn encoder_with() {
+    let mut codec = LinesCodec::new().with(|n: u32| n.to_string());
+    let mut buf = BytesMut::new();
+    codec.encode(42, &mut buf).unwrap();
+    assert_eq!(&buf[..], b"42\n");
+}
+
+#[test]
+fn combinators_in_framed() {
+    let codec = LinesCodec::new()
+        .and_then(|line| {
+            line.parse::<u32>().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, line))
+        })
+        .map(|n| n * 2)
+        .with(|n: u32| n.to_string());
+    let framed = Cursor::new(b"21\n".to_vec()).framed(codec);
+
+    let (n, framed) = framed.into_future().wait().map_err(|(e, _)| e).unwrap();
+    assert_eq!(Some(42), n);
+
+    let framed = framed.send(7).wait().unwrap();
+    assert_eq!(&framed.get_ref().get_ref()[..], b"21\n7\n");
+}
@@ -0,0 +1,196 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_io::io::{copy, copy_bidirectional, CopyBuilder};
+
+use futures::{Future, Poll};
+use futures::Async::{Ready, NotReady};
+
+use std::io::{self, Cursor, Read, Write};
+use std::collections::VecDeque;
+
+macro_rules! mock {
+    ($($x:expr,)*) => {{
+        let mut v = VecDeque::new();
+        v.extend(vec![$($x),*]);
+        Mock { reads: v, written: vec![], shut_down: false }
+    }};
+}
+
+#[test]
+fn copy_everything() {
+    let reader = mock! {
+        Ok(b"hello ".to_vec()),
+        Err(would_block()),
+        Ok(b"world".to_vec()),
+    };
+
+    let mut copy = copy(reader, Cursor::new(Vec::new()));
+    assert!(!copy.poll().unwrap().is_ready());
+
+    let (amt, _, writer) = match copy.poll().unwrap() {
+        Ready(v) => v,
+        NotReady => panic!("not ready"),
+    };
+    assert_eq!(amt, 11);
+    assert_eq!(writer.get_ref(), b"hello world");
+}
+
+#[test]
+fn copy_with_buffer_size() {
+    let reader: &[u8] = b"hello world";
+    let mut progress = vec![];
+
+    let (amt, _, writer) = CopyBuilder::new()
+        .buffer_size(4)
+        .copy(reader, Cursor::new(Vec::new()))
+        .progress(|n| progress.push(n))
+        .wait().unwrap();
+
+    assert_eq!(amt, 11);
+    assert_eq!(writer.get_ref(), b"hello world");
+    assert_eq!(progress, [4, 8, 11]);
+}
+
+#[test]
+fn copy_with_caller_buffer() {
+    let reader: &[u8] = b"hello world";
+    let buf = vec![0; 3].into_boxed_slice();
+
+    let (amt, _, writer, buf) = CopyBuilder::new()
+        .copy_with_buffer(buf, reader, Cursor::new(Vec::new()))
+        .progress(|n| assert!(n % 3 == 0 || n == 11))
+        .wait().unwrap();
+
+    assert_eq!(amt, 11);
+    assert_eq!(writer.get_ref(), b"hello world");
+
+    // The buffer is handed back, ready for the next copy.
+    assert_eq!(buf.len(), 3);
+    let reader: &[u8] = b"again";
+    let (amt, _, writer, _) = CopyBuilder::new()
+        .copy_with_buffer(buf, reader, Cursor::new(Vec::new()))
+        .wait().unwrap();
+
+    assert_eq!(amt, 5);
+    assert_eq!(writer.get_ref(), b"again");
+}
+
+#[test]
+fn copy_stops_at_limit() {
+    let (amt, reader, writer) = CopyBuilder::new()
+        .limit(3)
+        .copy(Cursor::new(b"hello world".to_vec()), Cursor::new(Vec::new()))
+        .wait().unwrap();
+
+    assert_eq!(amt, 3);
+    assert_eq!(reader.position(), 3);
+    assert_eq!(writer.get_ref(), b"hel");
+
+    // A zero limit never touches the reader.
+    let reader = mock! {
+        Ok(b"hello".to_vec()),
+    };
+    let (amt, reader, _) = CopyBuilder::new()
+        .limit(0)
+        .copy(reader, Cursor::new(Vec::new()))
+        .wait().unwrap();
+
+    assert_eq!(amt, 0);
+    assert_eq!(reader.reads.len(), 1);
+}
+
+#[test]
+fn copy_bidirectional_both_directions() {
+    let a = mock! {
+        Ok(b"ping".to_vec()),
+    };
+    let b = mock! {
+        Ok(b"pong!".to_vec()),
+    };
+
+    let (a_to_b, b_to_a, a, b) = copy_bidirectional(a, b).wait().unwrap();
+
+    assert_eq!(a_to_b, 4);
+    assert_eq!(b_to_a, 5);
+    assert_eq!(b.written, b"ping");
+    assert_eq!(a.written, b"pong!");
+    assert!(a.shut_down);
+    assert!(b.shut_down);
+}
+
+#[test]
+fn copy_bidirectional_propagates_half_close() {
+    let a = mock! {
+        Ok(b"request".to_vec()),
+    };
+    let b = mock! {
+        Err(would_block()),
+        Ok(b"response".to_vec()),
+    };
+
+    let mut copy = copy_bidirectional(a, b);
+
+    // `a` hits EOF and `b` gets shut down, but `b` is still being read.
+    assert!(!copy.poll().unwrap().is_ready());
+
+    let (a_to_b, b_to_a, a, b) = match copy.poll().unwrap() {
+        Ready(v) => v,
+        NotReady => panic!("not ready"),
+    };
+
+    assert_eq!(a_to_b, 7);
+    assert_eq!(b_to_a, 8);
+    assert_eq!(b.written, b"request");
+    assert_eq!(a.written, b"response");
+    assert!(a.shut_down);
+    assert!(b.shut_down);
+}
+
+// ===== Mock ======
+
+struct Mock {
+    reads: VecDeque<io::Result<Vec<u8>>>,
+    written: Vec<u8>,
+    shut_down: bool,
+}
+
+fn would_block() -> io::Error {
+    io::Error::new(io::ErrorKind::WouldBlock, "would block")
+}
+
+impl Read for Mock {
+    fn read(&mut self, dst: &mut [u8]) -> io::Result<usize> {
+        match self.reads.pop_front() {
+            Some(Ok(data)) => {
+                dst[..data.len()].copy_from_slice(&data[..]);
+                Ok(data.len())
+            }
+            Some(Err(e)) => Err(e),
+            None => Ok(0),
+        }
+    }
+}
+
+impl AsyncRead for Mock {
+}
+
+impl Write for Mock {
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        assert!(!self.shut_down, "write after shutdown");
+        self.written.extend_from_slice(src);
+        Ok(src.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Mock {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.shut_down = true;
+        Ok(().into())
+    }
+}
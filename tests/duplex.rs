@@ -0,0 +1,94 @@
+extern crate tokio_io;
+extern crate bytes;
+extern crate futures;
+
+use tokio_io::AsyncWrite;
+use tokio_io::io::{duplex, read_exact, read_to_end, write_all};
+use tokio_io::codec::length_delimited;
+use bytes::Bytes;
+use futures::{future, Future, Sink, Stream};
+
+use std::io::{self, Read, Write};
+
+#[test]
+fn write_then_read() {
+    let (a, b) = duplex(64);
+
+    let (a, _) = write_all(a, b"hello").wait().unwrap();
+    let (b, buf) = read_exact(b, [0; 5]).wait().unwrap();
+    assert_eq!(&buf, b"hello");
+
+    // And the other way around.
+    let (_, _) = write_all(b, b"world").wait().unwrap();
+    let (_, buf) = read_exact(a, [0; 5]).wait().unwrap();
+    assert_eq!(&buf, b"world");
+}
+
+#[test]
+fn full_buffer_would_block() {
+    future::lazy(|| {
+        let (mut a, mut b) = duplex(4);
+
+        assert_eq!(a.write(b"hello").unwrap(), 4);
+        assert_eq!(a.write(b"o").unwrap_err().kind(), io::ErrorKind::WouldBlock);
+
+        let mut buf = [0; 2];
+        assert_eq!(b.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf, b"he");
+
+        assert_eq!(a.write(b"o").unwrap(), 1);
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn empty_buffer_would_block() {
+    future::lazy(|| {
+        let (_a, mut b) = duplex(4);
+
+        let mut buf = [0; 4];
+        assert_eq!(b.read(&mut buf).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn shutdown_is_eof() {
+    let (mut a, b) = duplex(64);
+
+    assert_eq!(a.write(b"bye").unwrap(), 3);
+    assert!(a.shutdown().unwrap().is_ready());
+
+    let (_, buf) = read_to_end(b, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"bye");
+
+    assert_eq!(a.write(b"more").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn dropped_peer() {
+    let (mut a, b) = duplex(64);
+    drop(b);
+
+    let mut buf = [0; 4];
+    assert_eq!(a.read(&mut buf).unwrap(), 0);
+    assert_eq!(a.write(b"hello").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn drives_length_delimited() {
+    let (a, b) = duplex(3);
+
+    let write = length_delimited::FramedWrite::new(a)
+        .send_all(futures::stream::iter_ok::<_, io::Error>(vec![
+            Bytes::from(&b"hello"[..]),
+            Bytes::from(&b"world"[..]),
+        ]))
+        .and_then(|(mut io, _)| {
+            future::poll_fn(move || io.get_mut().shutdown())
+        });
+    let read = length_delimited::FramedRead::new(b).collect();
+
+    let (_, frames) = write.join(read).wait().unwrap();
+    assert_eq!(frames, vec!["hello", "world"]);
+}
@@ -2,10 +2,11 @@ extern crate tokio_io;
 extern crate bytes;
 extern crate futures;
 
-use futures::{Stream, Future};
+use futures::{Stream, Sink, Future};
 use std::io::{self, Read};
-use tokio_io::codec::{Framed, FramedParts, Decoder, Encoder};
+use tokio_io::codec::{Framed, FramedParts, Decoder, Encoder, LinesCodec};
 use tokio_io::AsyncRead;
+use tokio_io::io::{duplex, write_all};
 use bytes::{BytesMut, Buf, BufMut, IntoBuf, BigEndian};
 
 const INITIAL_CAPACITY: usize = 8 * 1024;
@@ -95,3 +96,116 @@ fn external_buf_does_not_shrink() {
     assert_eq!(readbuf.capacity(), INITIAL_CAPACITY * 2);
 }
 
+#[test]
+fn map_codec_keeps_buffers() {
+    let parts = FramedParts {
+        inner: DontReadIntoThis,
+        readbuf: b"\x00\x00\x00\x2ahello\n"[..].into(),
+        writebuf: b"\x00\x00\x00\x07"[..].into(),
+    };
+    let framed = Framed::from_parts(parts, U32Codec);
+
+    let (num, framed) = framed.into_future().wait().map_err(|e| e.0).unwrap();
+    assert_eq!(num, Some(42));
+
+    let framed = framed.map_codec(|_| LinesCodec::new());
+    let (line, framed) = framed.into_future().wait().map_err(|e| e.0).unwrap();
+    assert_eq!(line, Some("hello".to_string()));
+
+    let FramedParts { readbuf, writebuf, .. } = framed.into_parts();
+    assert!(readbuf.is_empty());
+    assert_eq!(&writebuf[..], b"\x00\x00\x00\x07");
+}
+
+#[test]
+fn split_and_reunite() {
+    let (a, b) = duplex(64);
+    let (_b, _) = write_all(b, b"one\ntwo\n").wait().unwrap();
+
+    let framed = a.framed(LinesCodec::new());
+    let (line, framed) = framed.into_future().wait().map_err(|e| e.0).unwrap();
+    assert_eq!(line, Some("one".to_string()));
+
+    let (stream, mut sink) = framed.into_halves();
+    assert!(stream.is_pair_of(&sink));
+    assert_eq!(&stream.read_buffer()[..], b"two\n");
+
+    assert!(sink.start_send("hi".to_string()).unwrap().is_ready());
+    assert_eq!(sink.write_buffer().len(), 3);
+
+    let (line, stream) = stream.into_future().wait().map_err(|e| e.0).unwrap();
+    assert_eq!(line, Some("two".to_string()));
+
+    let (framed, _) = stream.reunite(sink).unwrap();
+    let FramedParts { readbuf, writebuf, .. } = framed.into_parts();
+    assert!(readbuf.is_empty());
+    assert_eq!(&writebuf[..], b"hi\n");
+}
+
+// Counts the frames it encodes and decodes.
+#[derive(Clone)]
+struct Counter {
+    decoded: u32,
+    encoded: u32,
+}
+
+impl Decoder for Counter {
+    type Item = u32;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<u32>> {
+        let n = try!(U32Codec.decode(buf));
+        self.decoded += n.is_some() as u32;
+        Ok(n)
+    }
+}
+
+impl Encoder for Counter {
+    type Item = u32;
+    type Error = io::Error;
+
+    fn encode(&mut self, item: u32, dst: &mut BytesMut) -> io::Result<()> {
+        self.encoded += 1;
+        U32Codec.encode(item, dst)
+    }
+}
+
+#[test]
+fn reunite_hands_back_sink_codec() {
+    let (a, b) = duplex(64);
+    let (_b, _) = write_all(b, b"\x00\x00\x00\x01").wait().unwrap();
+
+    let (stream, sink) = a.framed(Counter { decoded: 0, encoded: 0 }).into_halves();
+    let sink = sink.send(7).wait().unwrap();
+    let (n, stream) = stream.into_future().wait().map_err(|e| e.0).unwrap();
+    assert_eq!(n, Some(1));
+
+    let (framed, sink_codec) = stream.reunite(sink).unwrap();
+    let (_, codec) = framed.into_parts_and_codec();
+    assert_eq!((codec.decoded, codec.encoded), (1, 0));
+    assert_eq!((sink_codec.decoded, sink_codec.encoded), (0, 1));
+}
+
+#[test]
+fn reunite_mismatch() {
+    let (a, _b) = duplex(64);
+    let (c, _d) = duplex(64);
+    let (stream1, sink1) = a.framed(LinesCodec::new()).into_halves();
+    let (stream2, sink2) = c.framed(LinesCodec::new()).into_halves();
+
+    let err = stream1.reunite(sink2).unwrap_err();
+    assert!(!err.0.is_pair_of(&err.1));
+    assert!(err.0.reunite(sink1).is_ok());
+    assert!(stream2.reunite(err.1).is_ok());
+}
+
+#[test]
+fn stream_split_is_not_shadowed() {
+    let (a, _b) = duplex(64);
+    let framed = a.framed(LinesCodec::new());
+
+    // `Stream::split` hands the sink first.
+    let (sink, stream) = framed.split();
+    let framed = stream.reunite(sink).unwrap();
+    drop(framed);
+}
@@ -1,12 +1,14 @@
 extern crate tokio_io;
 extern crate bytes;
 extern crate futures;
+extern crate iovec;
 
 use tokio_io::AsyncWrite;
-use tokio_io::codec::{Encoder, FramedWrite};
+use tokio_io::codec::{Builder, BytesCodec, EncodeBuf, Encoder, FramedWrite, FramedWriteParts};
 
-use futures::{Sink, Poll};
-use bytes::{BytesMut, BufMut, BigEndian};
+use futures::{Async, Sink, Poll};
+use bytes::{Buf, Bytes, BytesMut, BufMut, BigEndian};
+use iovec::IoVec;
 
 use std::io::{self, Write};
 use std::collections::VecDeque;
@@ -102,6 +104,129 @@ fn write_hits_backpressure() {
     assert_eq!(0, framed.get_ref().calls.len());
 }
 
+#[test]
+fn write_water_marks() {
+    let mock = mock! {
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+        Ok(b"\x00\x00\x00\x00".to_vec()),
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+        Ok(b"\x00\x00\x00\x01".to_vec()),
+        Ok(b"\x00\x00\x00\x02".to_vec()),
+    };
+
+    let mut framed = Builder::new()
+        .write_capacity(16)
+        .write_water_marks(4, 8)
+        .new_write(mock, U32Encoder);
+
+    assert!(framed.start_send(0).unwrap().is_ready());
+    assert!(framed.start_send(1).unwrap().is_ready());
+
+    // Over the high water mark, and blocked
+    assert!(!framed.start_send(2).unwrap().is_ready());
+
+    // Under the high water mark, but not under the low one yet
+    assert!(!framed.start_send(2).unwrap().is_ready());
+
+    assert!(framed.start_send(2).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
+#[test]
+fn write_bytes_without_copying() {
+    let payload = Bytes::from(vec![1; 1024]);
+
+    let mut framed = FramedWrite::new(Gather(vec![]), BytesCodec::new());
+    assert!(framed.start_send(Bytes::from("ab")).unwrap().is_ready());
+    assert!(framed.start_send(payload.clone()).unwrap().is_ready());
+    assert!(framed.start_send(Bytes::from("cd")).unwrap().is_ready());
+    assert!(framed.start_send(Bytes::from("ef")).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+
+    // One write, with small chunks coalesced and the payload as is
+    let writes = &framed.get_ref().0;
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[0].len(), 3);
+    assert_eq!(writes[0][0].1, 2);
+    assert_eq!(writes[0][1], (payload.as_ptr() as usize, 1024));
+    assert_eq!(writes[0][2].1, 4);
+}
+
+#[test]
+fn encode_buf_chunks() {
+    let mut buf = EncodeBuf::with_capacity(8);
+    buf.buffer_mut().put_slice(b"head");
+    buf.put_bytes(Bytes::from(vec![b'x'; 1024]));
+    buf.put_bytes(Bytes::from("tail"));
+    assert_eq!(buf.len(), 4 + 1024 + 4);
+
+    buf.advance(6);
+    assert_eq!(buf.bytes(), &[b'x'; 1022][..]);
+    buf.advance(1020);
+
+    let rest = buf.into_bytes_mut();
+    assert_eq!(&rest[..], b"xxtail");
+}
+
+#[test]
+fn replace_codec_keeps_buffer() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x01hi".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    assert!(framed.start_send(1).unwrap().is_ready());
+
+    let mut framed = framed.replace_codec(BytesCodec::new());
+    assert!(framed.start_send(Bytes::from("hi")).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
+#[test]
+fn write_parts_keep_unflushed_frames() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x01hi".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    assert!(framed.start_send(1).unwrap().is_ready());
+    assert_eq!(4, framed.write_buffer().len());
+
+    let FramedWriteParts { inner, writebuf } = framed.into_parts();
+    assert_eq!(&writebuf[..], b"\x00\x00\x00\x01");
+
+    let parts = FramedWriteParts { inner: inner, writebuf: writebuf };
+    let mut framed = FramedWrite::from_parts(parts, BytesCodec::new());
+    assert!(framed.start_send(Bytes::from("hi")).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
+#[test]
+fn write_parts_keep_builder_config() {
+    let mock = mock! {
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+        Ok(b"\x00\x00\x00\x00".to_vec()),
+        Ok(b"\x00\x00\x00\x01".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    assert!(framed.start_send(0).unwrap().is_ready());
+
+    let mut framed = Builder::new()
+        .write_water_marks(4, 4)
+        .write_from_parts(framed.into_parts(), U32Encoder);
+
+    // The frame carried over is already at the high water mark
+    assert!(!framed.start_send(1).unwrap().is_ready());
+    assert!(framed.start_send(1).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
 // ===== Mock ======
 
 struct Mock {
@@ -131,3 +256,28 @@ impl AsyncWrite for Mock {
         Ok(().into())
     }
 }
+
+// Records the address and length of the chunks given to each write
+struct Gather(Vec<Vec<(usize, usize)>>);
+
+impl Write for Gather {
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        self.0.push(vec![(src.as_ptr() as usize, src.len())]);
+        Ok(src.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Gather {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(().into())
+    }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        self.0.push(bufs.iter().map(|buf| (buf.as_ptr() as usize, buf.len())).collect());
+        Ok(Async::Ready(bufs.iter().fold(0, |n, buf| n + buf.len())))
+    }
+}
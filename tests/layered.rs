@@ -0,0 +1,86 @@
+extern crate tokio_io;
+extern crate bytes;
+
+use tokio_io::codec::{Decoder, Encoder, Layered, LinesCodec};
+use bytes::{BufMut, Bytes, BytesMut};
+
+use std::io;
+
+// Frames prefixed with a single length byte.
+struct ShortFrames;
+
+impl Decoder for ShortFrames {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        match src.first() {
+            Some(&len) if src.len() > len as usize => {
+                src.split_to(1);
+                Ok(Some(src.split_to(len as usize)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Encoder for ShortFrames {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, frame: Bytes, dst: &mut BytesMut) -> io::Result<()> {
+        dst.reserve(1 + frame.len());
+        dst.put_u8(frame.len() as u8);
+        dst.put(frame);
+        Ok(())
+    }
+}
+
+#[test]
+fn decode_one_item_per_frame() {
+    let mut codec = Layered::new(ShortFrames, LinesCodec::new());
+    let buf = &mut BytesMut::from(&b"\x06hello\n\x05world\x03ab"[..]);
+
+    assert_eq!(codec.decode(buf).unwrap(), Some("hello".to_string()));
+    assert_eq!(codec.decode(buf).unwrap(), Some("world".to_string()));
+    assert_eq!(codec.decode(buf).unwrap(), None);
+
+    buf.put_slice(b"c");
+    assert_eq!(codec.decode(buf).unwrap(), Some("abc".to_string()));
+    assert_eq!(codec.decode_eof(buf).unwrap(), None);
+}
+
+#[test]
+fn leftover_bytes_in_frame() {
+    let mut codec = Layered::new(ShortFrames, LinesCodec::new());
+    let buf = &mut BytesMut::from(&b"\x07one\ntwo"[..]);
+
+    let err = codec.decode(buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn empty_frame() {
+    let mut codec = Layered::new(ShortFrames, ShortFrames);
+    let buf = &mut BytesMut::from(&b"\x00"[..]);
+
+    let err = codec.decode(buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn encode_nested_frames() {
+    let mut codec = Layered::new(ShortFrames, LinesCodec::new());
+    let mut buf = BytesMut::new();
+
+    codec.encode("hello".to_string(), &mut buf).unwrap();
+    codec.encode("".to_string(), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x06hello\n\x01\n");
+
+    let mut codec = Layered::new(ShortFrames, ShortFrames);
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("hi"), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x03\x02hi");
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), "hi");
+}
@@ -1,10 +1,15 @@
 extern crate tokio_io;
+extern crate bytes;
 extern crate futures;
+extern crate iovec;
 
 use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_io::codec::{Decoder, Encoder, Layered, LinesCodec};
 use tokio_io::codec::length_delimited::*;
+use bytes::{Bytes, BytesMut};
+use iovec::IoVec;
 
-use futures::{Stream, Sink, Poll};
+use futures::{Future, Stream, Sink, Poll};
 use futures::Async::*;
 
 use std::io;
@@ -182,7 +187,42 @@ fn read_max_frame_len() {
             Ok(b"\x00\x00\x00\x09abcdefghi"[..].into()),
         });
 
-    assert_eq!(io.poll().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    let err = io.poll().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(ChecksumMismatch::from_io_error(&err).is_none());
+}
+
+#[test]
+fn read_max_frame_len_skip() {
+    let mut io = Builder::new()
+        .max_frame_length(5)
+        .on_frame_too_big(FrameTooBigPolicy::Skip)
+        .new_read(mock! {
+            Ok(b"\x00\x00\x00\x09abcd"[..].into()),
+            Ok(b"efg"[..].into()),
+            Ok(b"hi\x00\x00\x00\x03123\x00\x00"[..].into()),
+            Ok(b"\x00\x06abcdef\x00\x00\x00\x01x"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"123"[..].into())));
+    assert_eq!(io.decoder().skipped_frames(), 1);
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"x"[..].into())));
+    assert_eq!(io.decoder().skipped_frames(), 2);
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_max_frame_len_skip_collect() {
+    let io = Builder::new()
+        .max_frame_length(5)
+        .on_frame_too_big(FrameTooBigPolicy::Skip)
+        .new_read(mock! {
+            Ok(b"\x00\x00\x00\x02ab\x00\x00\x00\x09abcd"[..].into()),
+            Ok(b"efghi\x00\x00\x00\x03123"[..].into()),
+        });
+
+    let frames = io.collect().wait().unwrap();
+    assert_eq!(frames, vec![BytesMut::from(&b"ab"[..]), BytesMut::from(&b"123"[..])]);
 }
 
 #[test]
@@ -451,6 +491,507 @@ fn write_update_max_frame_len_in_flight() {
     assert!(io.get_ref().calls.is_empty());
 }
 
+#[test]
+fn codec_decode_frames() {
+    let mut codec = Builder::new()
+        .length_field_length(2)
+        .new_codec();
+    let buf = &mut BytesMut::from(&b"\x00\x03abc\x00\x02d"[..]);
+
+    assert_eq!(codec.decode(buf).unwrap().unwrap(), "abc");
+    assert_eq!(codec.decode(buf).unwrap(), None);
+    buf.extend_from_slice(b"e");
+    assert_eq!(codec.decode(buf).unwrap().unwrap(), "de");
+    assert_eq!(codec.decode_eof(buf).unwrap(), None);
+}
+
+#[test]
+fn codec_encode_frames() {
+    let mut codec = Builder::new()
+        .length_field_length(2)
+        .little_endian()
+        .new_codec();
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("abc"), &mut buf).unwrap();
+    codec.encode(Bytes::new(), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x03\x00abc\x00\x00");
+}
+
+#[test]
+fn codec_max_frame_len() {
+    let mut codec = LengthDelimitedCodec::new();
+    codec.set_max_frame_length(2);
+    assert_eq!(codec.max_frame_length(), 2);
+
+    let mut buf = BytesMut::new();
+    let err = codec.encode(Bytes::from("abc"), &mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let buf = &mut BytesMut::from(&b"\x00\x00\x00\x03abc"[..]);
+    let err = codec.decode(buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn codec_with_framed() {
+    let mut io = mock! {
+        Ok(b"\x00\x00\x00\x09abcdefghi"[..].into()),
+        Ok(b"\x00\x00\x00\x02"[..].into()),
+        Ok(b"ok"[..].into()),
+        Ok(Flush),
+    }.framed(LengthDelimitedCodec::new());
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"abcdefghi"[..].into())));
+    assert!(io.start_send(Bytes::from("ok")).unwrap().is_ready());
+    assert!(io.poll_complete().unwrap().is_ready());
+    assert!(io.get_ref().calls.is_empty());
+}
+
+#[test]
+fn codec_layered() {
+    let mut codec = Layered::new(LengthDelimitedCodec::new(), LinesCodec::new());
+    let mut buf = BytesMut::new();
+
+    codec.encode("hello".to_string(), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x00\x00\x00\x06hello\n");
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), "hello");
+}
+
+#[test]
+fn codec_resync_frame_too_big() {
+    let mut codec = Builder::new()
+        .length_field_length(1)
+        .max_frame_length(2)
+        .new_codec()
+        .recover();
+    let mut buf = BytesMut::from(&b"\x03a"[..]);
+
+    let err = codec.decode(&mut buf).unwrap().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(codec.decode(&mut buf).unwrap().is_none());
+
+    buf.extend_from_slice(b"bc\x02hi");
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap().unwrap(), b"hi"[..]);
+    assert!(codec.decode_eof(&mut buf).unwrap().is_none());
+}
+
+#[test]
+fn codec_resync_at_eof() {
+    let mut codec = Builder::new()
+        .length_field_length(1)
+        .new_codec()
+        .recover();
+    let mut buf = BytesMut::from(&b"\x03ab"[..]);
+
+    let err = codec.decode_eof(&mut buf).unwrap().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert!(codec.decode_eof(&mut buf).unwrap().is_none());
+}
+
+#[test]
+fn codec_resync_malformed_head() {
+    let mut codec = Builder::new()
+        .varint()
+        .new_codec()
+        .recover();
+    let mut buf = BytesMut::from(&b"\x80\x00abc"[..]);
+
+    let err = codec.decode(&mut buf).unwrap().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(codec.decode(&mut buf).unwrap_err().kind(), io::ErrorKind::Other);
+}
+
+#[test]
+fn codec_layered_resync() {
+    let outer = Builder::new()
+        .length_field_length(1)
+        .max_frame_length(2)
+        .new_codec();
+    let mut codec = Layered::new(outer, LinesCodec::new()).recover();
+    let mut buf = BytesMut::from(&b"\x03abc\x02\xff\n\x02a\n"[..]);
+
+    // Outer error, the frame is skipped
+    let err = codec.decode(&mut buf).unwrap().unwrap().unwrap_err();
+    assert!(err.get_ref().unwrap().is::<FrameTooBig>());
+
+    // Inner error, the frame is already consumed
+    let err = codec.decode(&mut buf).unwrap().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap().unwrap(), "a");
+    assert!(codec.decode_eof(&mut buf).unwrap().is_none());
+}
+
+#[test]
+fn read_varint_frames() {
+    let mut io = Builder::new()
+        .varint()
+        .new_read(mock! {
+            Ok(b"\x03abc\x80"[..].into()),
+            Ok(b"\x01"[..].into()),
+            Ok(vec![b'x'; 128].into()),
+            Ok(b"\x00"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"abc"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(Some(vec![b'x'; 128][..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(Some(b""[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_varint_adjustment_and_skip() {
+    let mut io = Builder::new()
+        .varint()
+        .length_field_offset(1)
+        .length_adjustment(2)
+        .num_skip(0)
+        .new_read(mock! {
+            Ok(b"\xfe\x03abc"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"\xfe\x03abc"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_varint_max_frame_len() {
+    let mut io = Builder::new()
+        .varint()
+        .max_frame_length(127)
+        .new_read(mock! {
+            Ok(b"\x80\x01"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_varint_rejects_non_canonical() {
+    let mut codec = Builder::new().varint().new_codec();
+
+    let buf = &mut BytesMut::from(&b"\x83\x00abc"[..]);
+    assert_eq!(codec.decode(buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut codec = Builder::new().varint().new_codec();
+    let buf = &mut BytesMut::from(&b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02"[..]);
+    assert_eq!(codec.decode(buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut codec = Builder::new().varint().new_codec();
+    let buf = &mut BytesMut::from(&b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x01"[..]);
+    assert_eq!(codec.decode(buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    // An incomplete varint just needs more data.
+    let mut codec = Builder::new().varint().new_codec();
+    let buf = &mut BytesMut::from(&b"\x80\x80"[..]);
+    assert_eq!(codec.decode(buf).unwrap(), None);
+}
+
+#[test]
+fn write_varint_frames() {
+    let mut codec = Builder::new()
+        .varint()
+        .length_adjustment(-1)
+        .new_codec();
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("abc"), &mut buf).unwrap();
+    codec.encode(Bytes::from(vec![b'x'; 127]), &mut buf).unwrap();
+    assert_eq!(&buf[..6], b"\x04abc\x80\x01");
+    assert_eq!(buf.len(), 6 + 127);
+
+    let mut codec = Builder::new().varint().new_codec();
+    let mut buf = BytesMut::new();
+    codec.encode(Bytes::new(), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x00");
+}
+
+#[test]
+fn read_header_frames() {
+    let mut io = Builder::new()
+        .length_field_offset(2)
+        .length_field_length(2)
+        .length_adjustment(1)
+        .new_header_read(mock! {
+            Ok(b"\x01\x07\x00\x02abc"[..].into()),
+            Ok(b"\x02\x00\x00\x00\xff"[..].into()),
+        });
+
+    match io.poll().unwrap() {
+        Ready(Some((header, payload))) => {
+            assert_eq!(header.prefix(), b"\x01\x07");
+            assert_eq!(header.length(), 2);
+            assert_eq!(&payload[..], b"abc");
+        }
+        v => panic!("unexpected {:?}", v),
+    }
+
+    match io.poll().unwrap() {
+        Ready(Some((header, payload))) => {
+            assert_eq!(header.into_prefix(), &b"\x02\x00"[..]);
+            assert_eq!(&payload[..], b"\xff");
+        }
+        v => panic!("unexpected {:?}", v),
+    }
+
+    assert!(io.poll().unwrap() == Ready(None));
+}
+
+#[test]
+fn write_header_frames() {
+    let mut io = Builder::new()
+        .length_field_offset(1)
+        .length_field_length(2)
+        .new_header_write(mock! {
+            Ok(b"\x09\x00\x03"[..].into()),
+            Ok(b"abc"[..].into()),
+            Ok(Flush),
+        });
+
+    let header = Header::new(BytesMut::from(&b"\x09"[..]));
+    assert!(io.start_send((header, "abc")).unwrap().is_ready());
+    assert!(io.poll_complete().unwrap().is_ready());
+    assert!(io.get_ref().calls.is_empty());
+}
+
+#[test]
+fn write_header_wrong_prefix_len() {
+    let mut io = Builder::new()
+        .length_field_offset(1)
+        .new_header_write(mock!());
+
+    let header = Header::new(BytesMut::from(&b"\x09\x09"[..]));
+    let err = io.start_send((header, "abc")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn codec_checksum_frames() {
+    let mut codec = Builder::new()
+        .length_field_length(1)
+        .checksum(Checksum::Crc32)
+        .new_codec();
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("abc"), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x03abc\x35\x24\x41\xc2");
+
+    codec.encode(Bytes::from("hello"), &mut buf).unwrap();
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), "abc");
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), "hello");
+    assert!(buf.is_empty());
+
+    let mut codec = Builder::new()
+        .little_endian()
+        .length_field_length(1)
+        .checksum(Checksum::Crc32c)
+        .new_codec();
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("abc"), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x03abc\xb7\x3f\x4b\x36");
+}
+
+#[test]
+fn read_checksum_mismatch() {
+    let mut io = Builder::new()
+        .length_field_length(1)
+        .checksum(Checksum::Crc32)
+        .new_read(mock! {
+            Ok(b"\x03abc\x35\x24\x41"[..].into()),
+            Ok(b"\xc2\x03abc\x00\x00\x00\x00"[..].into()),
+            Ok(b"\x00\x00\x00\x00\x00"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"abc"[..].into())));
+
+    let err = io.poll().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    {
+        let mismatch = ChecksumMismatch::from_io_error(&err).unwrap();
+        assert_eq!(mismatch.frame(), 1);
+        assert_eq!(mismatch.expected(), 0);
+        assert_eq!(mismatch.actual(), 0x352441c2);
+    }
+
+    // An empty frame has a zero checksum
+    assert_eq!(io.poll().unwrap(), Ready(Some(b""[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn write_checksum_frames() {
+    let mut io = Builder::new()
+        .checksum(Checksum::Crc32)
+        .new_write(mock! {
+            Ok(b"\x00\x00\x00\x03"[..].into()),
+            Ok(b"ab"[..].into()),
+            Ok(b"c"[..].into()),
+            Ok(b"\x35\x24\x41\xc2"[..].into()),
+            Ok(Flush),
+        });
+
+    assert!(io.start_send("abc").unwrap().is_ready());
+    assert!(io.poll_complete().unwrap().is_ready());
+    assert!(io.get_ref().calls.is_empty());
+}
+
+#[test]
+fn read_stream_events() {
+    let mut io = Builder::new()
+        .length_field_length(2)
+        .new_stream_read(mock! {
+            Ok(b"\x00\x09abc"[..].into()),
+            Err(would_block()),
+            Ok(b"defghi\x00\x00\x00\x02"[..].into()),
+            Ok(b"12"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Start(9))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Data(b"abc"[..].into()))));
+    assert_eq!(io.poll().unwrap(), NotReady);
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Data(b"defghi"[..].into()))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::End)));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Start(0))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::End)));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Start(2))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Data(b"12"[..].into()))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::End)));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_stream_checksum() {
+    let mut io = Builder::new()
+        .length_field_length(1)
+        .checksum(Checksum::Crc32)
+        .new_stream_read(mock! {
+            Ok(b"\x03ab"[..].into()),
+            Ok(b"c\x35\x24"[..].into()),
+            Ok(b"\x41\xc2\x01a\x00\x00\x00\x00"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Start(3))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Data(b"ab"[..].into()))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Data(b"c"[..].into()))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::End)));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Start(1))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Data(b"a"[..].into()))));
+
+    let err = io.poll().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(ChecksumMismatch::from_io_error(&err).unwrap().frame(), 1);
+}
+
+#[test]
+fn read_stream_truncated_frame() {
+    let mut io = Builder::new()
+        .length_field_length(1)
+        .new_stream_read(mock! {
+            Ok(b"\x05abc"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Start(5))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Data(b"abc"[..].into()))));
+    assert_eq!(io.poll().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn read_stream_ignores_max_frame_len() {
+    let mut io = Builder::new()
+        .length_field_length(1)
+        .max_frame_length(2)
+        .new_stream_read(mock! {
+            Ok(b"\x03abc"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Start(3))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Data(b"abc"[..].into()))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::End)));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_stream_max_frame_len() {
+    let mut io = Builder::new()
+        .length_field_length(1)
+        .max_stream_frame_length(2)
+        .new_stream_read(mock! {
+            Ok(b"\x03abc"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_stream_skip_frame_too_big() {
+    let mut io = Builder::new()
+        .length_field_length(1)
+        .max_stream_frame_length(2)
+        .checksum(Checksum::Crc32)
+        .on_frame_too_big(FrameTooBigPolicy::Skip)
+        .new_stream_read(mock! {
+            Ok(b"\x03abc\x00\x00"[..].into()),
+            Ok(b"\x00\x00\x00\x00\x00\x00\x00"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Skipped(3))));
+    assert_eq!(io.decoder().skipped_frames(), 1);
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::Start(0))));
+    assert_eq!(io.poll().unwrap(), Ready(Some(FrameEvent::End)));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn write_queued_frames() {
+    let mut io = Builder::new()
+        .length_field_length(1)
+        .max_queued_frames(3)
+        .new_write(Gather::new());
+
+    // Frames are queued without being written
+    assert!(io.start_send("a").unwrap().is_ready());
+    assert!(io.start_send("bc").unwrap().is_ready());
+    assert!(io.start_send("def").unwrap().is_ready());
+    assert!(io.get_ref().writes.is_empty());
+
+    // The queue is full, and the writer blocks
+    io.get_mut().blocked = true;
+    assert!(!io.start_send("g").unwrap().is_ready());
+
+    io.get_mut().blocked = false;
+    assert!(io.start_send("g").unwrap().is_ready());
+    assert!(io.poll_complete().unwrap().is_ready());
+
+    assert_eq!(io.get_ref().writes, vec![
+        b"\x01a\x02bc\x03def".to_vec(),
+        b"\x01g".to_vec(),
+    ]);
+}
+
+#[test]
+fn write_queued_bytes() {
+    let mut io = Builder::new()
+        .length_field_length(1)
+        .max_queued_frames(8)
+        .max_queued_bytes(6)
+        .checksum(Checksum::Crc32)
+        .new_write(Gather::new());
+
+    // A frame is accepted by an empty queue, whatever its size
+    assert!(io.start_send("abcdef").unwrap().is_ready());
+    assert!(io.start_send("a").unwrap().is_ready());
+    assert!(io.get_ref().writes.len() == 1);
+    assert!(io.poll_complete().unwrap().is_ready());
+
+    let writes = &io.get_ref().writes;
+    assert_eq!(writes.len(), 2);
+    assert_eq!(&writes[0][..7], b"\x06abcdef");
+    assert_eq!(writes[0].len(), 11);
+    assert_eq!(writes[1], b"\x01a\xe8\xb7\xbe\x43");
+}
+
 // ===== Test utils =====
 
 fn would_block() -> io::Error {
@@ -530,3 +1071,50 @@ impl From<Vec<u8>> for Op {
         Op::Data(src)
     }
 }
+
+// Records each write, gathering all the chunks it is given
+struct Gather {
+    writes: Vec<Vec<u8>>,
+    blocked: bool,
+}
+
+impl Gather {
+    fn new() -> Gather {
+        Gather {
+            writes: vec![],
+            blocked: false,
+        }
+    }
+}
+
+impl io::Write for Gather {
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        if self.blocked {
+            return Err(would_block());
+        }
+        self.writes.push(src.to_vec());
+        Ok(src.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Gather {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(Ready(()))
+    }
+
+    fn write_bufs(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        if self.blocked {
+            return Ok(NotReady);
+        }
+        let write = bufs.iter().fold(vec![], |mut write, buf| {
+            write.extend_from_slice(buf);
+            write
+        });
+        self.writes.push(write);
+        Ok(Ready(self.writes.last().unwrap().len()))
+    }
+}
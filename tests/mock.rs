@@ -0,0 +1,101 @@
+extern crate tokio_io;
+extern crate bytes;
+extern crate futures;
+
+use tokio_io::io::mock::Builder;
+use tokio_io::codec::length_delimited;
+use bytes::Bytes;
+use futures::{future, Future, Sink, Stream};
+
+use std::io::{self, Read, Write};
+
+#[test]
+fn scripted_reads_and_writes() {
+    let mut io = Builder::new()
+        .read(b"hello")
+        .write(b"world")
+        .build();
+
+    let mut buf = [0; 3];
+    assert_eq!(io.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"hel");
+    assert_eq!(io.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"lo");
+
+    assert_eq!(io.write(b"wor").unwrap(), 3);
+    assert_eq!(io.write(b"ldwide").unwrap(), 2);
+
+    assert_eq!(io.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn injected_wait_and_errors() {
+    future::lazy(|| {
+        let mut io = Builder::new()
+            .wait()
+            .read_error(io::Error::new(io::ErrorKind::Other, "boom"))
+            .write_error(io::Error::new(io::ErrorKind::BrokenPipe, "gone"))
+            .build();
+
+        let mut buf = [0; 4];
+        assert_eq!(io.read(&mut buf).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(io.read(&mut buf).unwrap_err().kind(), io::ErrorKind::Other);
+        assert_eq!(io.write(b"x").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn drives_length_delimited() {
+    let io = Builder::new()
+        .read(b"\x00\x00\x00\x03abc")
+        .wait()
+        .read(b"\x00\x00\x00\x02de")
+        .write(b"\x00\x00\x00\x02")
+        .write(b"ok")
+        .build();
+
+    let io = length_delimited::Framed::<_, Bytes>::new(io);
+
+    let (frame, io) = io.into_future().wait().map_err(|(e, _)| e).unwrap();
+    assert_eq!(frame.unwrap(), "abc");
+    let (frame, io) = io.into_future().wait().map_err(|(e, _)| e).unwrap();
+    assert_eq!(frame.unwrap(), "de");
+
+    io.send(Bytes::from(&b"ok"[..])).wait().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "first difference at byte 3")]
+fn write_mismatch_panics() {
+    let mut io = Builder::new()
+        .write(b"hello")
+        .build();
+
+    let _ = io.write(b"help");
+}
+
+#[test]
+#[should_panic(expected = "the script is over")]
+fn unexpected_write_panics() {
+    let mut io = Builder::new().build();
+    let _ = io.write(b"hello");
+}
+
+#[test]
+#[should_panic(expected = "unexpected read; next scripted step is write(b\"ping\")")]
+fn read_while_expecting_write_panics() {
+    let mut io = Builder::new()
+        .write(b"ping")
+        .build();
+
+    let _ = io.read(&mut [0; 4]);
+}
+
+#[test]
+#[should_panic(expected = "1 scripted steps left: read(b\"bye\")")]
+fn unfinished_script_panics() {
+    let _io = Builder::new()
+        .read(b"bye")
+        .build();
+}
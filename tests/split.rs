@@ -0,0 +1,174 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_io::io::{duplex, read_exact, read_to_end, shutdown, split_arc, split_ref, write_all};
+use tokio_io::io::ClosedBy;
+use futures::{Future, Poll};
+
+use std::io::{self, Cursor, Read, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[test]
+fn reunite_pair() {
+    let (a, b) = duplex(64);
+    let (r, w) = a.split();
+
+    assert!(r.is_pair_of(&w));
+    assert!(w.is_pair_of(&r));
+
+    let (w, _) = write_all(w, b"hello").wait().unwrap();
+    let a = r.reunite(w).unwrap();
+
+    // The reunited object is still connected to its peer.
+    let (b, buf) = read_exact(b, [0; 5]).wait().unwrap();
+    assert_eq!(&buf, b"hello");
+    let (_, _) = write_all(a, b"world").wait().unwrap();
+    let (_, buf) = read_exact(b, [0; 5]).wait().unwrap();
+    assert_eq!(&buf, b"world");
+}
+
+#[test]
+fn reunite_mismatch() {
+    let (a, b) = duplex(64);
+    let (r1, w1) = a.split();
+    let (r2, w2) = b.split();
+
+    assert!(!r1.is_pair_of(&w2));
+    assert!(!w1.is_pair_of(&r2));
+
+    let err = r1.reunite(w2).unwrap_err();
+    let (r1, w2) = (err.0, err.1);
+
+    // Nothing was lost, and the right pairs still fit together.
+    r1.reunite(w1).unwrap();
+    r2.reunite(w2).unwrap();
+}
+
+#[test]
+fn shutdown_closes_write_direction_only() {
+    let (a, b) = duplex(64);
+    let (r, w) = a.split();
+
+    let mut w = shutdown(w).wait().unwrap();
+    assert_eq!(w.closed_by(), Some(ClosedBy::Local));
+    assert_eq!(w.write(b"late").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+
+    // The peer sees EOF, but can still talk back.
+    let (b, buf) = read_to_end(b, Vec::new()).wait().unwrap();
+    assert!(buf.is_empty());
+    let (_, _) = write_all(b, b"still here").wait().unwrap();
+
+    let (r, buf) = read_to_end(r, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"still here");
+    assert_eq!(r.closed_by(), Some(ClosedBy::Peer));
+    assert_eq!(w.closed_by(), Some(ClosedBy::Local));
+}
+
+#[test]
+fn eof_is_sticky() {
+    let (a, b) = duplex(64);
+    let (mut r, _w) = a.split();
+
+    assert_eq!(r.closed_by(), None);
+    shutdown(b).wait().unwrap();
+
+    let mut buf = [0; 4];
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+    assert_eq!(r.closed_by(), Some(ClosedBy::Peer));
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn dropping_read_half_keeps_writes() {
+    let (a, b) = duplex(64);
+    let (r, w) = a.split();
+    drop(r);
+
+    let (w, _) = write_all(w, b"hello").wait().unwrap();
+    assert_eq!(w.closed_by(), None);
+
+    let (_, buf) = read_exact(b, [0; 5]).wait().unwrap();
+    assert_eq!(&buf, b"hello");
+}
+
+#[test]
+fn broken_pipe_is_peer_close() {
+    let (a, b) = duplex(64);
+    let (_r, mut w) = a.split();
+    drop(b);
+
+    assert_eq!(w.write(b"hello").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(w.closed_by(), Some(ClosedBy::Peer));
+}
+
+#[test]
+fn split_ref_halves() {
+    let io = Shared::new(b"hello");
+    {
+        let (r, w) = split_ref(&io);
+
+        let (_, buf) = read_to_end(r, Vec::new()).wait().unwrap();
+        assert_eq!(buf, b"hello");
+        write_all(w, b"world").wait().unwrap();
+    }
+    assert_eq!(*io.output.lock().unwrap(), b"world");
+}
+
+#[test]
+fn split_arc_halves_on_threads() {
+    let io = Arc::new(Shared::new(b"hello"));
+    let (r, w) = split_arc(io.clone());
+
+    let reader = thread::spawn(move || {
+        read_to_end(r, Vec::new()).wait().unwrap().1
+    });
+    let writer = thread::spawn(move || {
+        write_all(w, b"world").wait().unwrap();
+    });
+
+    assert_eq!(reader.join().unwrap(), b"hello");
+    writer.join().unwrap();
+    assert_eq!(*io.output.lock().unwrap(), b"world");
+}
+
+// An object readable and writable through `&Shared`, like a socket.
+struct Shared {
+    input: Mutex<Cursor<Vec<u8>>>,
+    output: Mutex<Vec<u8>>,
+}
+
+impl Shared {
+    fn new(input: &[u8]) -> Shared {
+        Shared {
+            input: Mutex::new(Cursor::new(input.to_vec())),
+            output: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl<'a> Read for &'a Shared {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.input.lock().unwrap().read(buf)
+    }
+}
+
+impl<'a> AsyncRead for &'a Shared {
+}
+
+impl<'a> Write for &'a Shared {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> AsyncWrite for &'a Shared {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(().into())
+    }
+}